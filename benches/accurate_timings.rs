@@ -0,0 +1,48 @@
+use advent_of_code::*;
+use iai_callgrind::{black_box, library_benchmark, library_benchmark_group, main};
+
+/// A benchmark group named after the year, with a `<year>_<day>` benchmark for each of its days.
+/// The default input of each day is read in the setup, so only solving it is measured, and days
+/// without an input are skipped.
+macro_rules! bench_year {
+	($year:ident, [$($day:ident),+]) => {
+		paste::paste! {
+			$(
+				#[library_benchmark]
+				#[bench::short({
+					let day = <aoc::$year::$day as clap::Parser>::parse_from(["bench"]);
+					match day.input.preload() {
+						Ok(()) => Some(day.input),
+						Err(err) => {
+							eprintln!("Skipping {} {}: {err:#}", stringify!($year), stringify!($day));
+							None
+						}
+					}
+				})]
+				fn [<$year _ $day:lower>](input: Option<aoc::helpers::DataFrom>) {
+					if let Some(input) = input {
+						let _ = black_box(input.solve(aoc::$year::$day::solve));
+					}
+				}
+			)+
+
+			library_benchmark_group!(
+				name = $year;
+				benchmarks = $([<$year _ $day:lower>]),+
+			);
+		}
+	};
+}
+
+// Years without any days yet have nothing to benchmark
+year2020_days!(bench_year!(year2020,));
+year2021_days!(bench_year!(year2021,));
+year2022_days!(bench_year!(year2022,));
+year2023_days!(bench_year!(year2023,));
+
+main!(
+	library_benchmark_groups = year2020,
+	year2021,
+	year2022,
+	year2023
+);
@@ -0,0 +1,5 @@
+#![no_main]
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+	advent_of_code::year2023_days!(advent_of_code_fuzz::fuzz_days!(year2023, data,));
+});
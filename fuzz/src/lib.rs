@@ -0,0 +1,14 @@
+/// Run one of the given days of a year, picked by the first byte of the data, on the rest of the
+/// data.  Errors are the expected result of most inputs, only a panic is a bug.
+#[macro_export]
+macro_rules! fuzz_days {
+	($year:ident, $data:ident, [$($day:ident),+ $(,)*]) => {{
+		let days: &[fn(&str)] = &[$(|input| {
+			let _ = advent_of_code::aoc::$year::$day::solve(input);
+		}),+];
+		if let Some((&which, input)) = $data.split_first() {
+			let input = String::from_utf8_lossy(input).into_owned();
+			days[usize::from(which) % days.len()](&input);
+		}
+	}};
+}
@@ -0,0 +1,31 @@
+#![warn(clippy::pedantic)]
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Solve both parts of `day` of `year` for `input`, giving the answers as a `(part1, part2)` tuple
+/// of strings, or raising a `ValueError` saying why the day couldn't be solved.  Other Python
+/// threads keep running while it solves.
+#[pyfunction]
+fn solve(py: Python<'_>, year: u16, day: u8, input: &str) -> PyResult<(String, String)> {
+	let answers = py
+		.allow_threads(|| advent_of_code::solve(year, day, input))
+		.map_err(|err| PyValueError::new_err(format!("{err:#}")))?;
+	Ok((answers.part1, answers.part2))
+}
+
+/// Every day that can be solved, in order, as `(year, day, title)` tuples.
+#[pyfunction]
+fn days() -> Vec<(u16, u8, String)> {
+	advent_of_code::days()
+		.into_iter()
+		.map(|day| (day.year, day.day, day.title))
+		.collect()
+}
+
+/// The Advent of Code solvers.
+#[pymodule]
+fn advent_of_code_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+	module.add_function(wrap_pyfunction!(solve, module)?)?;
+	module.add_function(wrap_pyfunction!(days, module)?)?;
+	Ok(())
+}
@@ -0,0 +1,24 @@
+import unittest
+
+import advent_of_code_py as aoc
+
+
+class SolveTest(unittest.TestCase):
+    def test_solves_a_day(self):
+        input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"
+        self.assertEqual(aoc.solve(2023, 1, input), ("142", "142"))
+
+    def test_errors_raise(self):
+        with self.assertRaises(ValueError):
+            aoc.solve(2023, 26, "")
+        with self.assertRaises(ValueError):
+            aoc.solve(2022, 1, "not a number\n")
+
+    def test_lists_days(self):
+        days = aoc.days()
+        self.assertIn((2022, 16, "Proboscidea Volcanium"), days)
+        self.assertIn((2023, 1, "Trebuchet?!"), days)
+
+
+if __name__ == "__main__":
+    unittest.main()
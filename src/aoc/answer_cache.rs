@@ -0,0 +1,226 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// What the answers of a day depend on: the source of the day standing in for the version of its
+/// implementation, the options it is run with, like `--impl`, and the input.  Changes to shared
+/// code outside of the day aren't noticed, which is what `--no-cache` is for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+	pub year: u16,
+	pub day: u8,
+	pub version: u64,
+	pub options: u64,
+	pub input: u64,
+}
+
+impl Key {
+	#[must_use]
+	pub fn new(year: u16, day: u8, source: &str, options: &str, input: &[u8]) -> Self {
+		Key {
+			year,
+			day,
+			version: fnv1a(source.as_bytes()),
+			options: fnv1a(options.as_bytes()),
+			input: fnv1a(input),
+		}
+	}
+
+	/// The key of the answers within the entry of the day.
+	fn answers_name(&self) -> String {
+		format!("{:016x}-{:016x}", self.options, self.input)
+	}
+
+	fn day_name(&self) -> String {
+		format!("{}:{}", self.year, self.day)
+	}
+}
+
+/// The options of a day parsed into `matches` besides its input, as `name=value` pairs, for the
+/// key of its answers.
+#[must_use]
+pub fn options(command: &clap::Command, matches: &clap::ArgMatches) -> String {
+	let mut options = Vec::new();
+	for arg in command.get_arguments() {
+		let id = arg.get_id().as_str();
+		if id == "input" {
+			continue;
+		}
+		if let Some(values) = matches.get_raw(id) {
+			let values: Vec<_> = values.map(|value| value.to_string_lossy()).collect();
+			options.push(format!("{id}={}", values.join(",")));
+		}
+	}
+	options.join(" ")
+}
+
+/// Whether the options in `matches` have the day write files, like renders and exported graphs.
+/// Those are only written when the day runs, so its answers aren't taken from the cache then.
+#[must_use]
+pub fn writes_artifacts(matches: &clap::ArgMatches) -> bool {
+	["render", "export_graph"]
+		.into_iter()
+		.any(|id| matches.try_contains_id(id).unwrap_or(false))
+}
+
+/// A hash that stays the same across builds and platforms, unlike those of the standard library.
+fn fnv1a(data: &[u8]) -> u64 {
+	data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+		(hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+	})
+}
+
+/// The cached answers of a day, only for the newest version of it seen.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Entry {
+	version: u64,
+	/// The answers by the hashes of the options and the input, as hex
+	answers: BTreeMap<String, (String, String)>,
+}
+
+/// The file of cached answers, in `$XDG_CACHE_HOME/advent_of_code`, or in `~/.cache` when that
+/// isn't set.
+fn path() -> Option<PathBuf> {
+	let dir = std::env::var_os("XDG_CACHE_HOME")
+		.filter(|dir| !dir.is_empty())
+		.map(PathBuf::from)
+		.or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".cache")))?;
+	Some(dir.join("advent_of_code").join("answers.json"))
+}
+
+fn load(path: &Path) -> BTreeMap<String, Entry> {
+	// A missing or unreadable cache is only a slower run
+	std::fs::read_to_string(path)
+		.ok()
+		.and_then(|data| serde_json::from_str(&data).ok())
+		.unwrap_or_default()
+}
+
+/// The answers cached for `key` in the user's cache, if any.
+#[must_use]
+pub fn get(key: &Key) -> Option<(String, String)> {
+	get_in(&path()?, key)
+}
+
+/// The answers cached for `key` in the cache file at `path`, if any.
+#[must_use]
+pub fn get_in(path: &Path, key: &Key) -> Option<(String, String)> {
+	let entry = load(path).remove(&key.day_name())?;
+	if entry.version != key.version {
+		return None;
+	}
+	entry.answers.get(&key.answers_name()).cloned()
+}
+
+/// Cache the answers for `key` in the user's cache, dropping those of older versions of the day.
+pub fn put(key: &Key, answers: (String, String)) -> anyhow::Result<()> {
+	let path = path().context("no cache directory, neither XDG_CACHE_HOME nor HOME are set")?;
+	put_in(&path, key, answers)
+}
+
+/// Cache the answers for `key` in the cache file at `path`, dropping those of older versions of
+/// the day.
+pub fn put_in(path: &Path, key: &Key, answers: (String, String)) -> anyhow::Result<()> {
+	let mut cache = load(path);
+	let entry = cache.entry(key.day_name()).or_default();
+	if entry.version != key.version {
+		*entry = Entry {
+			version: key.version,
+			answers: BTreeMap::new(),
+		};
+	}
+	entry.answers.insert(key.answers_name(), answers);
+	if let Some(dir) = path.parent() {
+		std::fs::create_dir_all(dir)
+			.with_context(|| format!("failed to create cache directory {}", dir.display()))?;
+	}
+	std::fs::write(path, serde_json::to_string_pretty(&cache)? + "\n")
+		.with_context(|| format!("failed to write answer cache {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{fnv1a, get_in, options, put_in, writes_artifacts, Key};
+	use std::path::PathBuf;
+
+	#[test]
+	fn fnv1a_is_stable() {
+		assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+		assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+	}
+
+	/// Removes the cache directory of a test, even when one of its assertions fails.
+	struct TempDir(PathBuf);
+
+	impl Drop for TempDir {
+		fn drop(&mut self) {
+			let _ = std::fs::remove_dir_all(&self.0);
+		}
+	}
+
+	#[test]
+	fn caches_by_version_and_input() {
+		let dir = TempDir(std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id())));
+		let path = dir.0.join("answers.json");
+		let get = |key| get_in(&path, key);
+		let put = |key, answers: (&str, &str)| {
+			put_in(&path, key, (answers.0.to_string(), answers.1.to_string())).unwrap();
+		};
+		let key = Key::new(2023, 1, "fn run() {}", "", b"1abc2\n");
+		assert_eq!(get(&key), None);
+		put(&key, ("12", "12"));
+		assert_eq!(get(&key), Some(("12".to_string(), "12".to_string())));
+		let other_input = Key::new(2023, 1, "fn run() {}", "", b"3abc4\n");
+		assert_eq!(get(&other_input), None);
+		put(&other_input, ("34", "34"));
+		assert!(get(&key).is_some());
+		// A new version of the day forgets the answers of the old one
+		let new_version = Key::new(2023, 1, "fn run() { todo!() }", "", b"1abc2\n");
+		assert_eq!(get(&new_version), None);
+		put(&new_version, ("0", "0"));
+		assert_eq!(get(&key), None);
+	}
+
+	#[test]
+	fn options_are_part_of_the_key() {
+		use crate::aoc::year2022::day16::Day16;
+		use clap::CommandFactory;
+
+		let dir = TempDir(std::env::temp_dir().join(format!("aoc-options-{}", std::process::id())));
+		let path = dir.0.join("answers.json");
+		let key = |args: &[&str]| {
+			let mut command = Day16::command();
+			let matches = command.try_get_matches_from_mut(args).unwrap();
+			Key::new(2022, 16, "", &options(&command, &matches), b"")
+		};
+		let permutations = key(&["day16"]);
+		assert_eq!(permutations, key(&["day16", "--impl", "permutations"]));
+		assert_eq!(permutations, key(&["day16", "other-input"]));
+		put_in(&path, &permutations, ("1".to_string(), "2".to_string())).unwrap();
+		assert!(get_in(&path, &permutations).is_some());
+		assert_eq!(get_in(&path, &key(&["day16", "--impl", "search"])), None);
+	}
+
+	#[test]
+	fn artifacts_skip_the_cache() {
+		use crate::aoc::year2022::day16::Day16;
+		use crate::aoc::year2022::day8::Day8;
+		use clap::CommandFactory;
+
+		let writes = |command: clap::Command, args: &[&str]| {
+			writes_artifacts(&command.try_get_matches_from(args).unwrap())
+		};
+		assert!(!writes(Day16::command(), &["day16"]));
+		assert!(writes(
+			Day16::command(),
+			&["day16", "--export-graph", "dot"]
+		));
+		assert!(!writes(Day8::command(), &["day8", "--scale", "2"]));
+		assert!(writes(Day8::command(), &["day8", "--render", "gif"]));
+		assert!(!writes(
+			crate::aoc::year2022::day1::Day1::command(),
+			&["day1"]
+		));
+	}
+}
@@ -0,0 +1,188 @@
+use crate::aoc::helpers::{AssumptionFailed, DataFrom};
+use crate::aoc::registry::{registered_day, RegisteredDay};
+use crate::AocApp;
+use anyhow::{bail, Context};
+use clap::Args;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+	pub year: u16,
+	pub day: u8,
+	/// The directory of inputs, every file in it is run
+	pub dir: PathBuf,
+	/// How many inputs to run at once
+	#[clap(short, long, default_value_t = 1)]
+	pub jobs: usize,
+}
+
+/// How running one input went.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+	Ok {
+		score1: String,
+		score2: String,
+	},
+	/// The input is valid but relies on something the day doesn't handle
+	Assumption(String),
+	Failed(String),
+	Panicked(String),
+}
+
+impl Status {
+	#[must_use]
+	pub fn name(&self) -> &'static str {
+		match self {
+			Status::Ok { .. } => "ok",
+			Status::Assumption(_) => "assumption",
+			Status::Failed(_) => "failed",
+			Status::Panicked(_) => "panicked",
+		}
+	}
+}
+
+/// Run the day once on `path`, timing only the solving and not the reading of the input.
+pub fn run_file(registered: &RegisteredDay, path: &Path, app: &AocApp) -> (Status, Duration) {
+	run_input(registered, DataFrom::file(path.to_path_buf()), app)
+}
+
+/// Run the day once on `input`, timing only the solving and not the reading of the input.
+pub fn run_input(registered: &RegisteredDay, input: DataFrom, app: &AocApp) -> (Status, Duration) {
+	let start = Instant::now();
+	let res = catch_unwind(AssertUnwindSafe(|| {
+		let run = registered.build(input)?;
+		let start = Instant::now();
+		let res = run(app);
+		Ok((res, start.elapsed()))
+	}));
+	let (res, taken) = match res {
+		Ok(Ok((res, taken))) => (res, taken),
+		Ok(Err(err)) => (Err(err), start.elapsed()),
+		Err(panic) => {
+			let msg = panic
+				.downcast_ref::<&str>()
+				.map(ToString::to_string)
+				.or_else(|| panic.downcast_ref::<String>().cloned())
+				.unwrap_or_else(|| "unknown panic".to_string());
+			return (Status::Panicked(msg), start.elapsed());
+		}
+	};
+	let status = match res {
+		Ok((score1, score2)) => Status::Ok { score1, score2 },
+		Err(err) => match err.downcast_ref::<AssumptionFailed>() {
+			Some(AssumptionFailed(msg)) => Status::Assumption(msg.clone()),
+			None => Status::Failed(format!("{err:#}")),
+		},
+	};
+	(status, taken)
+}
+
+pub fn run(args: &BatchArgs, app: &AocApp) -> anyhow::Result<()> {
+	let registered = registered_day(args.year, args.day)
+		.with_context(|| format!("{} day {} is not registered", args.year, args.day))?;
+	let mut files = Vec::new();
+	for entry in std::fs::read_dir(&args.dir)
+		.with_context(|| format!("failed to read directory {}", args.dir.display()))?
+	{
+		let entry = entry?;
+		if entry.file_type()?.is_file() {
+			files.push(entry.path());
+		}
+	}
+	if files.is_empty() {
+		bail!("no input files in {}", args.dir.display());
+	}
+	files.sort();
+
+	// Each worker takes the next file not yet taken, so slow inputs don't hold up the rest
+	let next = AtomicUsize::new(0);
+	let mut results: Vec<_> = std::thread::scope(|scope| {
+		let workers: Vec<_> = (0..args.jobs.clamp(1, files.len()))
+			.map(|_| {
+				scope.spawn(|| {
+					let mut results = Vec::new();
+					while let Some(path) = files.get(next.fetch_add(1, Ordering::Relaxed)) {
+						results.push((path, run_file(&registered, path, app)));
+					}
+					results
+				})
+			})
+			.collect();
+		// Panics of the days are caught by `run_file`, so the workers themselves can't panic
+		workers
+			.into_iter()
+			.flat_map(|worker| worker.join().unwrap_or_default())
+			.collect()
+	});
+	results.sort_by_key(|&(path, _)| path);
+
+	let name = |path: &Path| {
+		path.strip_prefix(&args.dir)
+			.unwrap_or(path)
+			.display()
+			.to_string()
+	};
+	let width = files
+		.iter()
+		.map(|path| name(path).len())
+		.max()
+		.unwrap_or(0)
+		.max(4);
+	println!(
+		"{:<width$} {:>20} {:>20} {:>12}  Status",
+		"File", "Step 1", "Step 2", "Time"
+	);
+	let mut failed = 0;
+	for (path, (status, taken)) in &results {
+		let (score1, score2, note) = match status {
+			Status::Ok { .. } if app.hide_scores => ("-", "-", ""),
+			Status::Ok { score1, score2 } => (score1.as_str(), score2.as_str(), ""),
+			Status::Assumption(msg) | Status::Failed(msg) | Status::Panicked(msg) => {
+				failed += 1;
+				// Only the first line, to keep to one row per input
+				("", "", msg.lines().next().unwrap_or_default())
+			}
+		};
+		let row = format!(
+			"{:<width$} {score1:>20} {score2:>20} {:>12}  {} {note}",
+			name(path),
+			format!("{taken:.2?}"),
+			status.name(),
+		);
+		println!("{}", row.trim_end());
+	}
+	if failed > 0 {
+		bail!("{failed} of {} input(s) didn't solve", results.len());
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{run_file, Status};
+	use crate::aoc::helpers::EMPTY_TUI_AOC_APP as APP;
+	use crate::aoc::registry::registered_day;
+
+	#[test]
+	fn statuses() {
+		let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let registered = registered_day(2022, 10).unwrap();
+		let run = |name: &str, input: &str| {
+			let path = dir.join(name);
+			std::fs::write(&path, input).unwrap();
+			run_file(&registered, &path, &APP).0
+		};
+		assert!(matches!(
+			run("short", "noop\naddx 3\n"),
+			Status::Assumption(_)
+		));
+		assert!(matches!(run("bad", "jump 3\n"), Status::Failed(_)));
+		let real = std::fs::read_to_string("inputs/2022/day10.input").unwrap();
+		assert!(matches!(run("real", &real), Status::Ok { .. }));
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
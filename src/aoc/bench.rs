@@ -0,0 +1,264 @@
+use crate::aoc::helpers::DataFrom;
+use crate::aoc::registry::{registered_days, Runner};
+use crate::AocApp;
+use anyhow::{bail, Context};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+	/// The days to benchmark, as `<year>:<day>`, or `<year>` for every day of a year, defaults to
+	/// every day
+	#[clap(value_name = "YEAR[:DAY]", value_parser = parse_selection)]
+	pub days: Vec<(u16, Option<u8>)>,
+	/// Roughly how long to measure each day for, in milliseconds
+	#[clap(long, default_value_t = 1000)]
+	pub time: u64,
+	/// The fewest runs to measure each day with, however slow it is
+	#[clap(long, default_value_t = 5)]
+	pub min_runs: usize,
+	/// The most runs to measure each day with, however fast it is
+	#[clap(long, default_value_t = 100_000)]
+	pub max_runs: usize,
+	/// Compare against the results saved in this file by `--save-baseline`
+	#[clap(long)]
+	pub baseline: Option<PathBuf>,
+	/// Save the results of this run to this file as JSON
+	#[clap(long)]
+	pub save_baseline: Option<PathBuf>,
+}
+
+fn parse_selection(s: &str) -> anyhow::Result<(u16, Option<u8>)> {
+	Ok(match s.split_once(':') {
+		Some((year, day)) => (year.parse()?, Some(day.parse()?)),
+		None => (s.parse()?, None),
+	})
+}
+
+/// The timings of one day, in nanoseconds, once outliers are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+	pub mean: f64,
+	/// Half the width of the 95% confidence interval of the mean
+	pub ci: f64,
+	pub runs: usize,
+	pub outliers: usize,
+}
+
+impl Summary {
+	/// Summarize the samples, dropping those outside of 1.5 interquartile ranges of the middle
+	/// half as outliers.
+	#[allow(clippy::cast_precision_loss)]
+	#[must_use]
+	pub fn from_samples(samples: &mut [f64]) -> Option<Self> {
+		samples.sort_unstable_by(f64::total_cmp);
+		let (q1, q3) = (quantile(samples, 0.25)?, quantile(samples, 0.75)?);
+		let (low, high) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+		let kept: Vec<f64> = samples
+			.iter()
+			.copied()
+			.filter(|s| (low..=high).contains(s))
+			.collect();
+		let n = kept.len();
+		let mean = kept.iter().sum::<f64>() / n as f64;
+		let ci = if n > 1 {
+			let variance = kept.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+			t_critical(n - 1) * (variance / n as f64).sqrt()
+		} else {
+			0.0
+		};
+		Some(Self {
+			mean,
+			ci,
+			runs: n,
+			outliers: samples.len() - n,
+		})
+	}
+
+	/// How much slower this is than `baseline`, as a fraction, and whether the confidence
+	/// intervals are far enough apart for that to be a real change.
+	#[must_use]
+	pub fn change(&self, baseline: &Summary) -> (f64, bool) {
+		let diff = self.mean - baseline.mean;
+		(diff / baseline.mean, diff.abs() > self.ci + baseline.ci)
+	}
+}
+
+/// Linearly interpolated quantile of sorted samples.
+#[allow(
+	clippy::cast_precision_loss,
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss
+)]
+fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+	let pos = (sorted.len().checked_sub(1)? as f64) * q;
+	let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+	Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - pos.floor()))
+}
+
+/// The two sided 95% critical value of Student's t distribution.
+fn t_critical(degrees_of_freedom: usize) -> f64 {
+	const TABLE: [f64; 30] = [
+		12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+		2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+		2.052, 2.048, 2.045, 2.042,
+	];
+	TABLE.get(degrees_of_freedom - 1).copied().unwrap_or(1.96)
+}
+
+/// Time a day, warming it up for a tenth of the budget to choose how many runs fit in the rest.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn measure(
+	run: &Runner,
+	app: &AocApp,
+	budget: Duration,
+	min_runs: usize,
+	max_runs: usize,
+) -> anyhow::Result<Option<Summary>> {
+	let warmup = Instant::now();
+	let mut warmup_runs = 0u32;
+	while warmup_runs == 0 || warmup.elapsed() < budget / 10 {
+		run(app)?;
+		warmup_runs += 1;
+	}
+	let estimate = warmup.elapsed() / warmup_runs;
+	let runs = (budget.as_nanos() / estimate.as_nanos().max(1))
+		.try_into()
+		.unwrap_or(usize::MAX)
+		.clamp(min_runs.max(1), max_runs.max(min_runs.max(1)));
+	let mut samples = Vec::with_capacity(runs);
+	for _ in 0..runs {
+		let start = Instant::now();
+		run(app)?;
+		samples.push(start.elapsed().as_nanos() as f64);
+	}
+	Ok(Summary::from_samples(&mut samples))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+#[must_use]
+pub fn format_nanos(nanos: f64) -> String {
+	format!("{:.2?}", Duration::from_nanos(nanos.max(0.0) as u64))
+}
+
+pub fn run(args: &BenchArgs, app: &AocApp) -> anyhow::Result<()> {
+	let baseline: BTreeMap<String, Summary> = match &args.baseline {
+		Some(path) => serde_json::from_str(
+			&std::fs::read_to_string(path)
+				.with_context(|| format!("failed to read baseline {}", path.display()))?,
+		)
+		.with_context(|| format!("invalid baseline {}", path.display()))?,
+		None => BTreeMap::new(),
+	};
+	let selected: Vec<_> = registered_days()
+		.into_iter()
+		.filter(|r| {
+			args.days.is_empty()
+				|| args
+					.days
+					.iter()
+					.any(|&(year, day)| year == r.year && day.is_none_or(|day| day == r.day))
+		})
+		.collect();
+	if selected.is_empty() {
+		bail!("no registered days match {:?}", args.days);
+	}
+
+	println!(
+		"{:<8} {:>7} {:>8} {:>12} {:>12} {:>12} {:>10}",
+		"Day", "Runs", "Outliers", "Mean", "± 95%", "Baseline", "Change"
+	);
+	let mut results = BTreeMap::new();
+	let mut failed = 0;
+	for registered in selected {
+		let name = format!("{}:{}", registered.year, registered.day);
+		let summary = registered
+			.build(DataFrom::internal(registered.year, registered.day))
+			.and_then(|run| {
+				let budget = Duration::from_millis(args.time);
+				measure(&run, app, budget, args.min_runs, args.max_runs)
+			});
+		let summary = match summary {
+			Ok(Some(summary)) => summary,
+			Ok(None) => {
+				println!("{name:<8} no runs were measured");
+				continue;
+			}
+			Err(err) => {
+				println!("{name:<8} failed: {err:#}");
+				failed += 1;
+				continue;
+			}
+		};
+		let (base, change) = match baseline.get(&name) {
+			Some(base) => {
+				let (change, significant) = summary.change(base);
+				let change = if significant {
+					format!("{:+.1}%", change * 100.0)
+				} else {
+					"~".to_string()
+				};
+				(format_nanos(base.mean), change)
+			}
+			None => (String::new(), String::new()),
+		};
+		let row = format!(
+			"{name:<8} {:>7} {:>8} {:>12} {:>12} {base:>12} {change:>10}",
+			summary.runs,
+			summary.outliers,
+			format_nanos(summary.mean),
+			format_nanos(summary.ci)
+		);
+		println!("{}", row.trim_end());
+		results.insert(name, summary);
+	}
+
+	if let Some(path) = &args.save_baseline {
+		std::fs::write(path, serde_json::to_string_pretty(&results)? + "\n")
+			.with_context(|| format!("failed to save baseline {}", path.display()))?;
+	}
+	if failed > 0 {
+		bail!("{failed} day(s) failed to run");
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Summary;
+
+	#[test]
+	fn rejects_outliers() {
+		let mut samples = vec![10.0, 11.0, 9.0, 10.0, 10.0, 11.0, 9.0, 1000.0];
+		let summary = Summary::from_samples(&mut samples).unwrap();
+		assert_eq!(summary.outliers, 1);
+		assert_eq!(summary.runs, 7);
+		assert!((summary.mean - 10.0).abs() < 1e-9);
+		// Sample standard deviation of 0.8165 over 7 runs with t = 2.447
+		assert!((summary.ci - 0.755).abs() < 1e-3);
+		assert_eq!(Summary::from_samples(&mut []), None);
+	}
+
+	#[test]
+	fn changes() {
+		let base = Summary {
+			mean: 100.0,
+			ci: 5.0,
+			runs: 10,
+			outliers: 0,
+		};
+		let same = Summary {
+			mean: 108.0,
+			..base
+		};
+		let slower = Summary {
+			mean: 120.0,
+			..base
+		};
+		assert_eq!(same.change(&base), (0.08, false));
+		assert_eq!(slower.change(&base), (0.2, true));
+	}
+}
@@ -0,0 +1,267 @@
+use crate::aoc::helpers::{DataFrom, ParseError};
+use anyhow::Context;
+use regex::Regex;
+
+/// The expected layout of a day's input, checked without solving the day.
+#[derive(Clone, Copy, Debug)]
+pub enum InputShape {
+	/// A rectangular grid made only of `cells`, where each of `unique` appears exactly once.
+	Grid {
+		cells: &'static str,
+		unique: &'static [char],
+	},
+	/// Every line matches the regex, which is described to the user by the second field.
+	Lines(&'static str, &'static str),
+	/// Blank line separated blocks, the first one with its own shape.
+	Blocks { first: BlockShape, rest: BlockShape },
+}
+
+/// The lines of a block, each given as a regex and its description to the user.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockShape {
+	pub lines: &'static [(&'static str, &'static str)],
+	/// Whether the last line can repeat, it must still appear at least once.
+	pub repeat_last: bool,
+}
+
+const MONKEY: BlockShape = BlockShape {
+	lines: &[
+		(r"^Monkey \d+:$", "`Monkey N:`"),
+		(
+			r"^  Starting items: (\d+(, \d+)*)?$",
+			"`  Starting items: N, N, ...`",
+		),
+		(
+			r"^  Operation: new = old [*+] (old|\d+)$",
+			"`  Operation: new = old OP (old|N)`",
+		),
+		(r"^  Test: divisible by \d+$", "`  Test: divisible by N`"),
+		(
+			r"^    If true: throw to monkey \d+$",
+			"`    If true: throw to monkey N`",
+		),
+		(
+			r"^    If false: throw to monkey \d+$",
+			"`    If false: throw to monkey N`",
+		),
+	],
+	repeat_last: false,
+};
+
+/// The known input shapes, by year and day.
+#[must_use]
+pub fn input_shape(year: u16, day: u8) -> Option<InputShape> {
+	Some(match (year, day) {
+		(2022, 8) => InputShape::Grid {
+			cells: "0123456789",
+			unique: &[],
+		},
+		(2022, 11) => InputShape::Blocks {
+			first: MONKEY,
+			rest: MONKEY,
+		},
+		(2022, 12) => InputShape::Grid {
+			cells: "abcdefghijklmnopqrstuvwxyzSE",
+			unique: &['S', 'E'],
+		},
+		(2022, 15) => InputShape::Lines(
+			r"^Sensor at x=-?\d+, y=-?\d+: closest beacon is at x=-?\d+, y=-?\d+$",
+			"`Sensor at x=N, y=N: closest beacon is at x=N, y=N`",
+		),
+		(2022, 16) => InputShape::Lines(
+			r"^Valve [A-Z]{2} has flow rate=\d+; (tunnels lead to valves|tunnel leads to valve) [A-Z]{2}(, [A-Z]{2})*$",
+			"`Valve XX has flow rate=N; tunnels lead to valves XX, XX, ...`",
+		),
+		(2023, 5) => InputShape::Blocks {
+			first: BlockShape {
+				lines: &[(r"^seeds:( \d+)+$", "`seeds: N N ...`")],
+				repeat_last: false,
+			},
+			rest: BlockShape {
+				lines: &[
+					(r"^[a-z]+-to-[a-z]+ map:$", "`source-to-destination map:`"),
+					(r"^\d+ \d+ \d+$", "`N N N`"),
+				],
+				repeat_last: true,
+			},
+		},
+		(2023, 10) => InputShape::Grid {
+			cells: "|-LJ7F.S",
+			unique: &['S'],
+		},
+		(2023, 11) => InputShape::Grid {
+			cells: ".#",
+			unique: &[],
+		},
+		_ => return None,
+	})
+}
+
+/// Check `input` against the shape of the given day, returning every problem found.
+pub fn check_input(year: u16, day: u8, input: &DataFrom) -> anyhow::Result<Vec<ParseError>> {
+	let shape = input_shape(year, day)
+		.with_context(|| format!("no known input shape for {year} day {day}"))?;
+	let data = input.as_str()?;
+	let mut problems = Vec::new();
+	if data.is_empty() {
+		problems.push(input.parse_error(data, "some input"));
+		return Ok(problems);
+	}
+	match shape {
+		InputShape::Grid { cells, unique } => check_grid(input, data, cells, unique, &mut problems),
+		InputShape::Lines(regex, description) => {
+			let regex = Regex::new(regex)?;
+			for line in data.lines() {
+				if !regex.is_match(line) {
+					problems.push(input.parse_error(line, description));
+				}
+			}
+		}
+		InputShape::Blocks { first, rest } => {
+			let first = (first, compile_block(first)?);
+			let rest = (rest, compile_block(rest)?);
+			for (idx, block) in input.blocks()?.enumerate() {
+				let (shape, regexes) = if idx == 0 { &first } else { &rest };
+				check_block(input, block, shape, regexes, &mut problems);
+			}
+		}
+	}
+	Ok(problems)
+}
+
+fn check_grid(
+	input: &DataFrom,
+	data: &str,
+	cells: &str,
+	unique: &[char],
+	problems: &mut Vec<ParseError>,
+) {
+	let width = data.lines().next().map_or(0, str::len);
+	for line in data.lines() {
+		if line.len() != width {
+			problems.push(input.parse_error(line, format!("a grid line of width {width}")));
+		}
+		for (idx, c) in line.char_indices() {
+			if !cells.contains(c) {
+				let found = &line[idx..idx + c.len_utf8()];
+				problems.push(input.parse_error(found, format!("one of `{cells}`")));
+			}
+		}
+	}
+	for &c in unique {
+		let mut found = data
+			.match_indices(c)
+			.map(|(idx, s)| &data[idx..idx + s.len()]);
+		match (found.next(), found.next()) {
+			(None, _) => problems.push(input.parse_error(
+				&data[data.len()..],
+				format!("exactly one `{c}` in the grid"),
+			)),
+			(Some(_), Some(second)) => {
+				problems.push(input.parse_error(second, format!("only one `{c}` in the grid")));
+			}
+			(Some(_), None) => (),
+		}
+	}
+}
+
+fn compile_block(shape: BlockShape) -> anyhow::Result<Vec<Regex>> {
+	Ok(shape
+		.lines
+		.iter()
+		.map(|(regex, _)| Regex::new(regex))
+		.collect::<Result<_, _>>()?)
+}
+
+fn check_block(
+	input: &DataFrom,
+	block: &str,
+	shape: &BlockShape,
+	regexes: &[Regex],
+	problems: &mut Vec<ParseError>,
+) {
+	let mut lines = block.lines();
+	for (idx, regex) in regexes.iter().enumerate() {
+		let description = shape.lines[idx].1;
+		let Some(line) = lines.next() else {
+			problems.push(
+				input.parse_error(&block[block.len()..], format!("a line like {description}")),
+			);
+			return;
+		};
+		if !regex.is_match(line) {
+			problems.push(input.parse_error(line, description));
+		}
+	}
+	let last = regexes.len() - 1;
+	for line in lines {
+		if shape.repeat_last {
+			if !regexes[last].is_match(line) {
+				problems.push(input.parse_error(line, shape.lines[last].1));
+			}
+		} else {
+			problems.push(input.parse_error(line, "a blank line to end the block"));
+		}
+	}
+}
+
+/// Print every problem found with the input and fail if there were any.
+pub fn run(year: u16, day: u8, input: &DataFrom) -> anyhow::Result<()> {
+	let problems = check_input(year, day, input)?;
+	for problem in &problems {
+		println!("{problem}\n");
+	}
+	if problems.is_empty() {
+		println!("{input} looks like a valid input for {year} day {day}");
+		Ok(())
+	} else {
+		anyhow::bail!(
+			"found {} problem(s) in the input for {year} day {day}",
+			problems.len()
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::check_input;
+	use crate::aoc::helpers::DataFrom;
+
+	#[test]
+	fn internal_inputs_are_valid() {
+		for (year, day) in [
+			(2022, 8),
+			(2022, 11),
+			(2022, 12),
+			(2022, 15),
+			(2022, 16),
+			(2023, 5),
+			(2023, 10),
+			(2023, 11),
+		] {
+			let problems = check_input(year, day, &DataFrom::internal(year, day)).unwrap();
+			assert!(problems.is_empty(), "{year} day {day}: {problems:?}");
+		}
+	}
+
+	#[test]
+	fn reports_every_problem() {
+		let input = DataFrom::from("S-7\n|x|\nL-JS\n");
+		let problems = check_input(2023, 10, &input).unwrap();
+		let found = problems
+			.iter()
+			.map(|p| (p.line, p.column, p.found.as_str()))
+			.collect::<Vec<_>>();
+		assert_eq!(found, [(2, 2, "x"), (3, 1, "L-JS"), (3, 4, "S")]);
+
+		let input = DataFrom::from("seeds: 1 2\n\nseed-to-soil map:\n1 2\n\nbad\n");
+		let problems = check_input(2023, 5, &input).unwrap();
+		let found = problems.iter().map(|p| p.line).collect::<Vec<_>>();
+		assert_eq!(found, [4, 6, 6]);
+	}
+
+	#[test]
+	fn unknown_day() {
+		assert!(check_input(2020, 1, &DataFrom::from("1\n")).is_err());
+	}
+}
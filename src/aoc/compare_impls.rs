@@ -0,0 +1,83 @@
+use crate::aoc::bench::{format_nanos, measure};
+use crate::aoc::helpers::DataFrom;
+use crate::aoc::registry::registered_day;
+use crate::AocApp;
+use anyhow::{bail, Context};
+use clap::Args;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Args)]
+pub struct CompareImplsArgs {
+	pub year: u16,
+	pub day: u8,
+	/// The input to run them all on, defaults to the internal input of the day
+	pub input: Option<DataFrom>,
+	/// Roughly how long to time each implementation for, in milliseconds, one that takes longer
+	/// than this to run once is only run the once
+	#[clap(long, default_value_t = 1000)]
+	pub time: u64,
+}
+
+pub fn run(args: &CompareImplsArgs, app: &AocApp) -> anyhow::Result<()> {
+	let registered = registered_day(args.year, args.day)
+		.with_context(|| format!("{} day {} is not registered", args.year, args.day))?;
+	let implementations = registered.implementations();
+	if implementations.is_empty() {
+		bail!(
+			"{} day {} has only the one implementation",
+			args.year,
+			args.day
+		);
+	}
+	let input = args
+		.input
+		.clone()
+		.unwrap_or_else(|| DataFrom::internal(args.year, args.day));
+	let budget = Duration::from_millis(args.time);
+
+	let width = implementations.iter().map(String::len).max().unwrap_or(0);
+	println!(
+		"{:<width$} {:>20} {:>20} {:>12} {:>12}",
+		"Impl", "Step 1", "Step 2", "Mean", "± 95%"
+	);
+	let mut answers = Vec::with_capacity(implementations.len());
+	for implementation in &implementations {
+		let run = registered
+			.build_impl(input.clone(), implementation)
+			.with_context(|| format!("failed to build `{implementation}`"))?;
+		let start = Instant::now();
+		let (score1, score2) =
+			run(app).with_context(|| format!("`{implementation}` failed to run"))?;
+		let once = start.elapsed();
+		let (mean, ci) = if once < budget {
+			let summary =
+				measure(&run, app, budget, 1, 100_000)?.context("no runs were measured")?;
+			(format_nanos(summary.mean), format_nanos(summary.ci))
+		} else {
+			(format!("{once:.2?}"), String::new())
+		};
+		let (shown1, shown2) = if app.hide_scores {
+			("-", "-")
+		} else {
+			(score1.as_str(), score2.as_str())
+		};
+		let row = format!("{implementation:<width$} {shown1:>20} {shown2:>20} {mean:>12} {ci:>12}");
+		println!("{}", row.trim_end());
+		answers.push((implementation, score1, score2));
+	}
+
+	let (first, first1, first2) = &answers[0];
+	let differing: Vec<_> = answers
+		.iter()
+		.filter(|(_, score1, score2)| (score1, score2) != (first1, first2))
+		.map(|(implementation, _, _)| format!("`{implementation}`"))
+		.collect();
+	if !differing.is_empty() {
+		bail!(
+			"the answers of {} differ from `{first}`",
+			differing.join(", ")
+		);
+	}
+	println!("All {} implementations agree", answers.len());
+	Ok(())
+}
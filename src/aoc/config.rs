@@ -0,0 +1,246 @@
+use anyhow::{bail, Context};
+use clap::Command;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The name of the config file in the current directory, overriding the user's config.
+pub const LOCAL_FILE: &str = "aoc.toml";
+
+/// Defaults for the command line, read from `$XDG_CONFIG_HOME/advent_of_code/config.toml` (or
+/// `~/.config` when that isn't set) and then `./aoc.toml`, the latter overriding the former.
+/// Anything given on the command line overrides both.
+///
+/// ```toml
+/// verbose = 1
+/// hide_scores = true
+/// format = "json"
+/// inputs_dir = "~/aoc/inputs"
+/// artifacts_dir = "renders"
+/// no_cache = false
+///
+/// [days.2022.16]
+/// impl = "search"
+/// ```
+///
+/// The per-day options are named as on the command line of the day, by their long name or, for
+/// positional ones like `input`, their name in its help.  Relative paths are relative to the
+/// config file they are in.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+	pub verbose: Option<u8>,
+	pub hide_scores: Option<bool>,
+	pub format: Option<String>,
+	pub inputs_dir: Option<PathBuf>,
+	pub artifacts_dir: Option<PathBuf>,
+	pub no_cache: Option<bool>,
+	/// The options of each day, by year then day
+	pub days: BTreeMap<String, BTreeMap<String, BTreeMap<String, toml::Value>>>,
+}
+
+/// The config loaded by [`Config::load`], for the days run without parsing their arguments, like
+/// those of `run-all`.
+static LOADED: OnceCell<Config> = OnceCell::new();
+
+impl Config {
+	/// The user's config merged with the local one, or the default one if neither exist.
+	pub fn load() -> anyhow::Result<Self> {
+		let mut merged = toml::Table::new();
+		for path in user_path().into_iter().chain([PathBuf::from(LOCAL_FILE)]) {
+			if let Some(table) = read(&path)? {
+				merge(&mut merged, table);
+			}
+		}
+		Config::deserialize(toml::Value::Table(merged)).context("invalid config")
+	}
+
+	/// Keep this config for the days that aren't parsed from the command line, see
+	/// [`day_command`].  Only the first call has any effect.
+	pub fn install(self) {
+		let _ = LOADED.set(self);
+	}
+
+	/// Make the config the defaults of the arguments of `command`, the command of
+	/// [`crate::AocApp`].
+	pub fn apply(&self, mut command: Command) -> anyhow::Result<Command> {
+		let path = |path: &PathBuf| path.as_os_str().to_owned();
+		let defaults = [
+			("verbose", self.verbose.map(|v| v.to_string().into())),
+			(
+				"hide_scores",
+				self.hide_scores.map(|v| v.to_string().into()),
+			),
+			("format", self.format.clone().map(Into::into)),
+			("inputs_dir", self.inputs_dir.as_ref().map(path)),
+			("artifacts_dir", self.artifacts_dir.as_ref().map(path)),
+			("no_cache", self.no_cache.map(|v| v.to_string().into())),
+		];
+		for (id, value) in defaults {
+			if let Some(value) = value {
+				command = command.mut_arg(id, |arg| arg.default_value(value));
+			}
+		}
+		for (year, days) in &self.days {
+			let Some(year_command) = command.find_subcommand(year) else {
+				bail!("invalid config, no year {year}");
+			};
+			let mut year_command = year_command.clone();
+			for (day, options) in days {
+				let Some(day_command) = year_command.find_subcommand(day) else {
+					bail!("invalid config, no day {day} of {year}");
+				};
+				let day_command = apply_day(year, day, options, day_command.clone())?;
+				year_command = year_command.mut_subcommand(day, |_| day_command);
+			}
+			command = command.mut_subcommand(year, |_| year_command);
+		}
+		Ok(command)
+	}
+}
+
+/// The command of a day with the defaults of its options from the installed config, if any.
+pub fn day_command(year: &str, day: &str, command: Command) -> anyhow::Result<Command> {
+	match LOADED
+		.get()
+		.and_then(|config| config.days.get(year)?.get(day))
+	{
+		Some(options) => apply_day(year, day, options, command),
+		None => Ok(command),
+	}
+}
+
+fn apply_day(
+	year: &str,
+	day: &str,
+	options: &BTreeMap<String, toml::Value>,
+	mut command: Command,
+) -> anyhow::Result<Command> {
+	for (name, value) in options {
+		let Some(id) = command
+			.get_arguments()
+			.find(|arg| arg.get_long() == Some(name) || arg.get_id() == name)
+			.map(|arg| arg.get_id().clone())
+		else {
+			bail!("invalid config, day {day} of {year} has no option {name}");
+		};
+		let value = match value {
+			toml::Value::String(s) => s.clone(),
+			toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+				value.to_string()
+			}
+			_ => {
+				bail!("invalid config, option {name} of day {day} of {year} must be a single value")
+			}
+		};
+		command = command.mut_arg(id, |arg| arg.default_value(value));
+	}
+	Ok(command)
+}
+
+/// The user's config file, in `$XDG_CONFIG_HOME/advent_of_code`, or in `~/.config` when that
+/// isn't set.
+fn user_path() -> Option<PathBuf> {
+	let dir = std::env::var_os("XDG_CONFIG_HOME")
+		.filter(|dir| !dir.is_empty())
+		.map(PathBuf::from)
+		.or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".config")))?;
+	Some(dir.join("advent_of_code").join("config.toml"))
+}
+
+/// The config file at `path`, with its paths made relative to where it is, if it exists.
+fn read(path: &Path) -> anyhow::Result<Option<toml::Table>> {
+	let data = match std::fs::read_to_string(path) {
+		Ok(data) => data,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+		Err(e) => {
+			return Err(e).with_context(|| format!("failed to read config {}", path.display()))
+		}
+	};
+	let mut table: toml::Table = data
+		.parse()
+		.with_context(|| format!("invalid config {}", path.display()))?;
+	let dir = path.parent().unwrap_or(Path::new(""));
+	for key in ["inputs_dir", "artifacts_dir"] {
+		if let Some(toml::Value::String(s)) = table.get_mut(key) {
+			*s = resolve(dir, s).to_string_lossy().into_owned();
+		}
+	}
+	Ok(Some(table))
+}
+
+/// A path from a config file in `dir`, with a leading `~` being the home directory.
+fn resolve(dir: &Path, path: &str) -> PathBuf {
+	if let Some(rest) = path.strip_prefix("~/") {
+		if let Some(home) = std::env::var_os("HOME") {
+			return PathBuf::from(home).join(rest);
+		}
+	}
+	dir.join(path)
+}
+
+/// Merge `from` into `into`, with the values of `from` winning, and tables merged key by key.
+fn merge(into: &mut toml::Table, from: toml::Table) {
+	for (key, value) in from {
+		match (into.get_mut(&key), value) {
+			(Some(toml::Value::Table(into)), toml::Value::Table(from)) => merge(into, from),
+			(_, value) => {
+				into.insert(key, value);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{merge, Config};
+	use crate::AocApp;
+	use clap::{CommandFactory, FromArgMatches};
+	use serde::Deserialize;
+
+	fn config(user: &str, local: &str) -> Config {
+		let mut merged = toml::Table::new();
+		merge(&mut merged, user.parse().unwrap());
+		merge(&mut merged, local.parse().unwrap());
+		Config::deserialize(toml::Value::Table(merged)).unwrap()
+	}
+
+	#[test]
+	fn local_overrides_user_and_arguments_override_both() {
+		let config = config(
+			"hide_scores = true\nformat = \"json\"\n[days.2022.16]\nimpl = \"permutations\"\n",
+			"format = \"text\"\n[days.2022.16]\nimpl = \"search\"\n",
+		);
+		let parse = |args: &[&str]| {
+			let mut matches = config
+				.apply(AocApp::command())
+				.unwrap()
+				.try_get_matches_from(args)
+				.unwrap();
+			AocApp::from_arg_matches_mut(&mut matches).unwrap()
+		};
+		let app = parse(&["aoc", "2022", "16"]);
+		assert!(app.hide_scores);
+		assert_eq!(app.format, crate::OutputFormat::Text);
+		let crate::AocAppCommand::Run(crate::aoc::AocYear::Year2022 {
+			day: crate::aoc::year2022::Year2022::Day16(day),
+		}) = app.command
+		else {
+			panic!("expected 2022 day 16");
+		};
+		assert_eq!(
+			day.implementation,
+			crate::aoc::year2022::day16::Implementation::Search
+		);
+		let app = parse(&["aoc", "--format", "json", "run-all"]);
+		assert_eq!(app.format, crate::OutputFormat::Json);
+	}
+
+	#[test]
+	fn unknown_settings_are_errors() {
+		let unknown_day_option: Config = toml::from_str("[days.2022.16]\nnope = 1\n").unwrap();
+		assert!(unknown_day_option.apply(AocApp::command()).is_err());
+		assert!(toml::from_str::<Config>("nope = 1\n").is_err());
+	}
+}
@@ -0,0 +1,469 @@
+use crate::aoc::bench::{format_nanos, measure, Summary};
+use crate::aoc::helpers::DataFrom;
+use crate::aoc::registry::registered_day;
+use crate::AocApp;
+use anyhow::{bail, Context};
+use clap::Args;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Makes valid inputs for a day, as large as asked for.
+#[derive(Clone, Copy, Debug)]
+pub struct Generator {
+	/// What the size counts
+	pub unit: &'static str,
+	/// The smallest size that makes a valid input, which `scale` starts from by default
+	pub min_size: usize,
+	generate: fn(&mut StdRng, usize) -> String,
+}
+
+impl Generator {
+	/// The same seed and size always make the same input.
+	#[must_use]
+	pub fn generate(&self, seed: u64, size: usize) -> String {
+		(self.generate)(&mut StdRng::seed_from_u64(seed), size.max(self.min_size))
+	}
+}
+
+/// The known generators, by year and day.
+#[must_use]
+pub fn generator(year: u16, day: u8) -> Option<Generator> {
+	Some(match (year, day) {
+		(2022, 8) => Generator {
+			unit: "trees per side",
+			min_size: 1,
+			generate: tree_heights,
+		},
+		(2022, 15) => Generator {
+			unit: "sensors",
+			min_size: 1,
+			generate: sensors,
+		},
+		(2023, 10) => Generator {
+			unit: "tiles per side",
+			min_size: 3,
+			generate: pipe_maze,
+		},
+		(2023, 12) => Generator {
+			unit: "springs per row",
+			min_size: 1,
+			generate: spring_rows,
+		},
+		_ => return None,
+	})
+}
+
+/// A square forest of random heights.
+fn tree_heights(rng: &mut StdRng, size: usize) -> String {
+	let mut out = String::with_capacity((size + 1) * size);
+	for _ in 0..size {
+		out.extend((0..size).map(|_| char::from(b'0' + rng.gen_range(0..10))));
+		out.push('\n');
+	}
+	out
+}
+
+/// Sensors spread over the search area, none of which reach one hidden spot, so the distress
+/// beacon is always somewhere.
+fn sensors(rng: &mut StdRng, size: usize) -> String {
+	const MAX: i64 = 4_000_000;
+	let hidden = (rng.gen_range(0..=MAX), rng.gen_range(0..=MAX));
+	let mut out = String::with_capacity(size * 64);
+	for _ in 0..size {
+		let (x, y, range) = loop {
+			let (x, y) = (rng.gen_range(0..=MAX), rng.gen_range(0..=MAX));
+			let distance = (x - hidden.0).abs() + (y - hidden.1).abs();
+			if distance >= 2 {
+				break (x, y, distance - 1);
+			}
+		};
+		// The closest beacon is anywhere on the edge of the sensor's range
+		let dx = rng.gen_range(-range..=range);
+		let dy = if rng.gen_bool(0.5) { 1 } else { -1 } * (range - dx.abs());
+		let (bx, by) = (x + dx, y + dy);
+		writeln!(
+			out,
+			"Sensor at x={x}, y={y}: closest beacon is at x={bx}, y={by}"
+		)
+		.expect("writing to a string can't fail");
+	}
+	out
+}
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+/// The tiles around the loop, all but the ground connect to something
+const JUNK: &[u8] = b".|-LJ7F";
+
+/// A single loop around a random tree of 3x3 blocks, with the middle of each block inside of it,
+/// surrounded by junk pipes.  Each block on its own is a ring of pipes, and the rings of blocks
+/// joined in the tree are merged by swapping a pair of facing pipes for two between them.
+#[allow(clippy::too_many_lines)]
+fn pipe_maze(rng: &mut StdRng, size: usize) -> String {
+	let blocks = size / 3;
+	// A margin of ground so the outermost junk can't connect to the start
+	let width = blocks * 3 + 2;
+	let mut tree = vec![false; blocks * blocks];
+	let mut edges = Vec::with_capacity(blocks * blocks);
+	let mut frontier = Vec::with_capacity(blocks * 4);
+	let first = rng.gen_range(0..tree.len());
+	tree[first] = true;
+	let push_frontier = |frontier: &mut Vec<(usize, usize)>, node: usize| {
+		let (x, y) = (node % blocks, node / blocks);
+		if x > 0 {
+			frontier.push((node, node - 1));
+		}
+		if x + 1 < blocks {
+			frontier.push((node, node + 1));
+		}
+		if y > 0 {
+			frontier.push((node, node - blocks));
+		}
+		if y + 1 < blocks {
+			frontier.push((node, node + blocks));
+		}
+	};
+	push_frontier(&mut frontier, first);
+	// Leave about a third of the blocks out of the tree as junk
+	let mut remaining = (tree.len() * 2 / 3).saturating_sub(1);
+	while remaining > 0 && !frontier.is_empty() {
+		let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+		if !tree[to] {
+			tree[to] = true;
+			edges.push((from.min(to), from.max(to)));
+			push_frontier(&mut frontier, to);
+			remaining -= 1;
+		}
+	}
+
+	let mut pipes = vec![0u8; width * width];
+	let idx = |block: usize, x: usize, y: usize| {
+		let (bx, by) = (block % blocks, block / blocks);
+		(1 + by * 3 + y) * width + 1 + bx * 3 + x
+	};
+	// Set or clear the connection between two neighbouring tiles
+	let mut link = |a: usize, b: usize, on: bool| {
+		let (da, db) = if b == a + 1 {
+			(RIGHT, LEFT)
+		} else if b == a + width {
+			(DOWN, UP)
+		} else {
+			unreachable!("tiles {a} and {b} are not neighbours")
+		};
+		if on {
+			pipes[a] |= da;
+			pipes[b] |= db;
+		} else {
+			pipes[a] &= !da;
+			pipes[b] &= !db;
+		}
+	};
+	for block in (0..tree.len()).filter(|&block| tree[block]) {
+		for (a, b) in [(0, 0), (1, 0), (0, 2), (1, 2)] {
+			link(idx(block, a, b), idx(block, a + 1, b), true);
+		}
+		for (a, b) in [(0, 0), (0, 1), (2, 0), (2, 1)] {
+			link(idx(block, a, b), idx(block, a, b + 1), true);
+		}
+	}
+	for (a, b) in edges {
+		if b == a + 1 {
+			link(idx(a, 2, 0), idx(a, 2, 1), false);
+			link(idx(b, 0, 0), idx(b, 0, 1), false);
+			link(idx(a, 2, 0), idx(b, 0, 0), true);
+			link(idx(a, 2, 1), idx(b, 0, 1), true);
+		} else {
+			link(idx(a, 0, 2), idx(a, 1, 2), false);
+			link(idx(b, 0, 0), idx(b, 1, 0), false);
+			link(idx(a, 0, 2), idx(b, 0, 0), true);
+			link(idx(a, 1, 2), idx(b, 1, 0), true);
+		}
+	}
+
+	let mut map: Vec<u8> = pipes
+		.iter()
+		.enumerate()
+		.map(|(i, &pipe)| {
+			let (x, y) = (i % width, i / width);
+			match pipe {
+				_ if x == 0 || y == 0 || x == width - 1 || y == width - 1 => b'.',
+				0 => JUNK[rng.gen_range(0..JUNK.len())],
+				p if p == UP | DOWN => b'|',
+				p if p == LEFT | RIGHT => b'-',
+				p if p == UP | RIGHT => b'L',
+				p if p == UP | LEFT => b'J',
+				p if p == DOWN | LEFT => b'7',
+				p if p == DOWN | RIGHT => b'F',
+				p => unreachable!("tile {i} has the connections {p:04b}"),
+			}
+		})
+		.collect();
+	let looped: Vec<usize> = (0..pipes.len()).filter(|&i| pipes[i] != 0).collect();
+	let start = looped[rng.gen_range(0..looped.len())];
+	map[start] = b'S';
+	// Only the pipes of the loop may connect to the start
+	for near in [start - 1, start + 1, start - width, start + width] {
+		if pipes[near] == 0 {
+			map[near] = b'.';
+		}
+	}
+
+	let mut out = String::with_capacity((width + 1) * width);
+	for line in map.chunks(width) {
+		out.extend(line.iter().map(|&b| char::from(b)));
+		out.push('\n');
+	}
+	out
+}
+
+/// Rows of groups of damaged springs, with a few springs of each row unknown so the number of
+/// arrangements stays countable however long the rows are.
+fn spring_rows(rng: &mut StdRng, size: usize) -> String {
+	const ROWS: usize = 10;
+	let mut out = String::with_capacity(ROWS * size * 2);
+	for _ in 0..ROWS {
+		let mut row = Vec::with_capacity(size);
+		let mut groups = Vec::with_capacity(size / 2);
+		loop {
+			let gap = rng.gen_range(usize::from(!groups.is_empty())..=3);
+			let len = rng.gen_range(1..=4);
+			if row.len() + gap + len > size {
+				break;
+			}
+			row.extend(std::iter::repeat_n(b'.', gap));
+			row.extend(std::iter::repeat_n(b'#', len));
+			groups.push(len);
+		}
+		if groups.is_empty() {
+			row.push(b'#');
+			groups.push(1);
+		}
+		row.resize(size, b'.');
+		for _ in 0..3 {
+			let at = rng.gen_range(0..size);
+			row[at] = b'?';
+		}
+		out.extend(row.iter().map(|&b| char::from(b)));
+		out.push(' ');
+		for (i, len) in groups.iter().enumerate() {
+			if i > 0 {
+				out.push(',');
+			}
+			write!(out, "{len}").expect("writing to a string can't fail");
+		}
+		out.push('\n');
+	}
+	out
+}
+
+pub fn run_gen(year: u16, day: u8, size: usize, seed: u64) -> anyhow::Result<()> {
+	let generator =
+		generator(year, day).with_context(|| format!("no input generator for {year} day {day}"))?;
+	print!("{}", generator.generate(seed, size));
+	Ok(())
+}
+
+#[derive(Debug, Args)]
+pub struct ScaleArgs {
+	pub year: u16,
+	pub day: u8,
+	/// The sizes to run, defaults to doubling from the smallest valid size
+	#[clap(long, value_delimiter = ',')]
+	pub sizes: Vec<usize>,
+	/// How many doubling sizes to run when `--sizes` isn't given
+	#[clap(long, default_value_t = 8)]
+	pub steps: u32,
+	/// Roughly how long to measure each size for, in milliseconds
+	#[clap(long, default_value_t = 500)]
+	pub time: u64,
+	/// Stop before the next size once one run takes longer than this many milliseconds
+	#[clap(long, default_value_t = 10_000)]
+	pub max_time: u64,
+	/// The seed of the generated inputs
+	#[clap(long, default_value_t = 0)]
+	pub seed: u64,
+}
+
+/// A fitted curve of time against size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fit {
+	/// `time = c * size^exponent`
+	Power { exponent: f64, r2: f64 },
+	/// `time = c * factor^size`
+	Exponential { factor: f64, r2: f64 },
+}
+
+/// Least squares fit of `y = a + b * x`, giving `(b, r²)`.
+#[allow(clippy::cast_precision_loss)]
+fn linear_fit(points: &[(f64, f64)]) -> (f64, f64) {
+	let n = points.len() as f64;
+	let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+	let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+	let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+	let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+	let syy: f64 = points.iter().map(|p| (p.1 - mean_y).powi(2)).sum();
+	let slope = sxy / sxx;
+	let r2 = if syy == 0.0 {
+		1.0
+	} else {
+		sxy * sxy / (sxx * syy)
+	};
+	(slope, r2)
+}
+
+/// Fit both a power and an exponential curve to `(size, nanoseconds)` points, giving the one
+/// that fits best.
+#[allow(clippy::cast_precision_loss)]
+#[must_use]
+pub fn fit(points: &[(usize, f64)]) -> Option<Fit> {
+	if points.len() < 3 {
+		return None;
+	}
+	let log_log: Vec<_> = points
+		.iter()
+		.map(|&(size, time)| ((size as f64).ln(), time.ln()))
+		.collect();
+	let lin_log: Vec<_> = points
+		.iter()
+		.map(|&(size, time)| (size as f64, time.ln()))
+		.collect();
+	let (exponent, power_r2) = linear_fit(&log_log);
+	let (rate, exponential_r2) = linear_fit(&lin_log);
+	// Only call it exponential when it's clearly the better fit, as both fit a short range well
+	Some(if exponential_r2 > power_r2 + 0.01 && rate > 0.0 {
+		Fit::Exponential {
+			factor: rate.exp(),
+			r2: exponential_r2,
+		}
+	} else {
+		Fit::Power {
+			exponent,
+			r2: power_r2,
+		}
+	})
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn run_scale(args: &ScaleArgs, app: &AocApp) -> anyhow::Result<()> {
+	let generator = generator(args.year, args.day)
+		.with_context(|| format!("no input generator for {} day {}", args.year, args.day))?;
+	let registered = registered_day(args.year, args.day)
+		.with_context(|| format!("{} day {} is not registered", args.year, args.day))?;
+	let sizes = if args.sizes.is_empty() {
+		(0..args.steps)
+			.map(|step| generator.min_size.max(10) << step)
+			.collect()
+	} else {
+		args.sizes.clone()
+	};
+
+	println!(
+		"{:>10} {:>12} {:>12} {:>8}",
+		"Size", "Mean", "± 95%", "Growth"
+	);
+	let mut points: Vec<(usize, f64)> = Vec::with_capacity(sizes.len());
+	for size in sizes {
+		let input = DataFrom::owned(generator.generate(args.seed, size));
+		let run = registered.build(input)?;
+		let summary: Summary = measure(&run, app, Duration::from_millis(args.time), 1, 10_000)
+			.with_context(|| format!("failed to run size {size}"))?
+			.context("no runs were measured")?;
+		// How the time grew against the size since the last one, as a power of the size
+		let growth = match points.last() {
+			Some(&(last_size, last_time)) if last_size != size => format!(
+				"n^{:.2}",
+				(summary.mean / last_time).ln() / (size as f64 / last_size as f64).ln()
+			),
+			_ => String::new(),
+		};
+		let row = format!(
+			"{size:>10} {:>12} {:>12} {growth:>8}",
+			format_nanos(summary.mean),
+			format_nanos(summary.ci),
+		);
+		println!("{}", row.trim_end());
+		points.push((size, summary.mean));
+		if summary.mean > (args.max_time * 1_000_000) as f64 {
+			println!("Stopping, a run took longer than {}ms", args.max_time);
+			break;
+		}
+	}
+
+	match fit(&points) {
+		Some(Fit::Power { exponent, r2 }) => {
+			println!(
+				"Time grows like n^{exponent:.2}, with n the {} (R² = {r2:.3})",
+				generator.unit
+			);
+		}
+		Some(Fit::Exponential { factor, r2 }) => println!(
+			"Time grows exponentially, ×{factor:.4} per one more of the {} (R² = {r2:.3})",
+			generator.unit
+		),
+		None if points.len() < 3 => bail!("need at least 3 sizes to fit a curve"),
+		None => {}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{fit, generator, Fit};
+	use crate::aoc::helpers::DataFrom;
+	use crate::aoc::helpers::EMPTY_TUI_AOC_APP as APP;
+	use crate::aoc::registry::registered_day;
+
+	#[test]
+	fn generated_inputs_solve() {
+		for (year, day, size) in [
+			(2022, 8, 20),
+			(2022, 15, 10),
+			(2023, 10, 30),
+			(2023, 12, 40),
+		] {
+			let input = generator(year, day).unwrap().generate(1, size);
+			let run = registered_day(year, day)
+				.unwrap()
+				.build(DataFrom::owned(input))
+				.unwrap();
+			run(&APP).unwrap_or_else(|err| panic!("{year} day {day}: {err:#}"));
+		}
+	}
+
+	#[test]
+	fn pipe_maze_answers() {
+		// Each block of the tree adds 8 pipes to the loop and 1 tile inside of it
+		let input = generator(2023, 10).unwrap().generate(7, 60);
+		let tiles = input.lines().flat_map(str::chars).count();
+		let (loop_half, inside) = registered_day(2023, 10)
+			.unwrap()
+			.build(DataFrom::owned(input))
+			.unwrap()(&APP)
+		.unwrap();
+		let inside: usize = inside.parse().unwrap();
+		assert_eq!(loop_half.parse::<usize>().unwrap(), inside * 4);
+		assert!(inside > 0 && inside < tiles);
+	}
+
+	#[test]
+	fn fits() {
+		let square: Vec<_> = (1..8u32)
+			.map(|n| (n as usize * 100, f64::from(n * n) * 50.0))
+			.collect();
+		let Some(Fit::Power { exponent, r2 }) = fit(&square) else {
+			panic!("expected a power fit");
+		};
+		assert!((exponent - 2.0).abs() < 1e-9 && r2 > 0.999);
+		let doubling: Vec<_> = (1..20i32)
+			.map(|n| (n.unsigned_abs() as usize, 2f64.powi(n)))
+			.collect();
+		let Some(Fit::Exponential { factor, .. }) = fit(&doubling) else {
+			panic!("expected an exponential fit");
+		};
+		assert!((factor - 2.0).abs() < 1e-9);
+	}
+}
@@ -0,0 +1,253 @@
+use crate::AocApp;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::fmt::{Display, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+	/// Graphviz DOT
+	Dot,
+	/// XML in the graphml format
+	Graphml,
+	/// JSON with a list of nodes and a list of edges
+	Json,
+}
+
+impl GraphFormat {
+	fn extension(self) -> &'static str {
+		match self {
+			GraphFormat::Dot => "dot",
+			GraphFormat::Graphml => "graphml",
+			GraphFormat::Json => "json",
+		}
+	}
+}
+
+/// The common options of days that build a graph.
+#[derive(Debug, Clone, Args)]
+pub struct GraphArgs {
+	/// Write the parsed graph to `<year>-<day>-graph.<ext>` in the artifacts directory, with the
+	/// solution highlighted
+	#[clap(long, value_enum)]
+	pub export_graph: Option<GraphFormat>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Node {
+	pub label: String,
+	pub weight: String,
+	pub highlight: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Edge {
+	pub source: usize,
+	pub target: usize,
+	pub weight: String,
+	pub highlight: bool,
+}
+
+/// A directed graph as exported, independent of how a day stores it.  Nodes and edges are
+/// referred to by the order they were added in.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportGraph {
+	pub nodes: Vec<Node>,
+	pub edges: Vec<Edge>,
+}
+
+impl ExportGraph {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Build from a petgraph graph, with node indices kept as they are.
+	#[cfg(any(feature = "year2020", feature = "year2022"))]
+	pub fn from_petgraph<N, E>(
+		graph: &petgraph::Graph<N, E>,
+		mut node: impl FnMut(petgraph::graph::NodeIndex, &N) -> (String, String),
+		mut edge: impl FnMut(&E) -> String,
+	) -> Self {
+		let mut export = Self::new();
+		for idx in graph.node_indices() {
+			let (label, weight) = node(idx, &graph[idx]);
+			export.add_node(label, weight);
+		}
+		for e in graph.raw_edges() {
+			export.add_edge(e.source().index(), e.target().index(), edge(&e.weight));
+		}
+		export
+	}
+
+	pub fn add_node(&mut self, label: impl Display, weight: impl Display) -> usize {
+		self.nodes.push(Node {
+			label: label.to_string(),
+			weight: weight.to_string(),
+			highlight: false,
+		});
+		self.nodes.len() - 1
+	}
+
+	pub fn add_edge(&mut self, source: usize, target: usize, weight: impl Display) -> usize {
+		self.edges.push(Edge {
+			source,
+			target,
+			weight: weight.to_string(),
+			highlight: false,
+		});
+		self.edges.len() - 1
+	}
+
+	pub fn highlight_node(&mut self, node: usize) {
+		self.nodes[node].highlight = true;
+	}
+
+	/// Highlight every edge from `source` to `target`.
+	pub fn highlight_edges(&mut self, source: usize, target: usize) {
+		for edge in &mut self.edges {
+			if edge.source == source && edge.target == target {
+				edge.highlight = true;
+			}
+		}
+	}
+
+	/// Highlight the nodes of `path` and the edges between them.
+	pub fn highlight_path(&mut self, path: &[usize]) {
+		for &node in path {
+			self.highlight_node(node);
+		}
+		for step in path.windows(2) {
+			self.highlight_edges(step[0], step[1]);
+		}
+	}
+
+	/// Write to `<year>-<day>-graph.<ext>` if `args` asks for it.
+	pub fn export(&self, app: &AocApp, args: &GraphArgs, year: u16, day: u8) -> anyhow::Result<()> {
+		let Some(format) = args.export_graph else {
+			return Ok(());
+		};
+		let path = app.artifact_path(year, day, "graph", format.extension())?;
+		std::fs::write(path, self.render(format)?)?;
+		Ok(())
+	}
+
+	pub fn render(&self, format: GraphFormat) -> anyhow::Result<String> {
+		let mut out = String::new();
+		match format {
+			GraphFormat::Dot => {
+				out.push_str("digraph {\n");
+				for (idx, node) in self.nodes.iter().enumerate() {
+					write!(
+						out,
+						"\t{idx} [label=\"{}\\n{}\"",
+						escape_dot(&node.label),
+						escape_dot(&node.weight)
+					)?;
+					if node.highlight {
+						out.push_str(", color=red, penwidth=2");
+					}
+					out.push_str("]\n");
+				}
+				for edge in &self.edges {
+					write!(
+						out,
+						"\t{} -> {} [label=\"{}\"",
+						edge.source,
+						edge.target,
+						escape_dot(&edge.weight)
+					)?;
+					if edge.highlight {
+						out.push_str(", color=red, penwidth=2");
+					}
+					out.push_str("]\n");
+				}
+				out.push_str("}\n");
+			}
+			GraphFormat::Graphml => {
+				out.push_str(concat!(
+					"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+					"<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+					"\t<key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+					"\t<key id=\"weight\" for=\"all\" attr.name=\"weight\" attr.type=\"string\"/>\n",
+					"\t<key id=\"highlight\" for=\"all\" attr.name=\"highlight\" attr.type=\"boolean\"/>\n",
+					"\t<graph edgedefault=\"directed\">\n",
+				));
+				for (idx, node) in self.nodes.iter().enumerate() {
+					writeln!(
+						out,
+						"\t\t<node id=\"n{idx}\"><data key=\"label\">{}</data><data key=\"weight\">{}</data><data key=\"highlight\">{}</data></node>",
+						escape_xml(&node.label),
+						escape_xml(&node.weight),
+						node.highlight
+					)?;
+				}
+				for edge in &self.edges {
+					writeln!(
+						out,
+						"\t\t<edge source=\"n{}\" target=\"n{}\"><data key=\"weight\">{}</data><data key=\"highlight\">{}</data></edge>",
+						edge.source,
+						edge.target,
+						escape_xml(&edge.weight),
+						edge.highlight
+					)?;
+				}
+				out.push_str("\t</graph>\n</graphml>\n");
+			}
+			GraphFormat::Json => {
+				out = serde_json::to_string_pretty(self)?;
+				out.push('\n');
+			}
+		}
+		Ok(out)
+	}
+}
+
+fn escape_dot(s: &str) -> String {
+	s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+	s.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{ExportGraph, GraphFormat};
+
+	fn graph() -> ExportGraph {
+		let mut graph = ExportGraph::new();
+		let a = graph.add_node("a", 1);
+		let b = graph.add_node("\"b\"", 2);
+		let c = graph.add_node("c<", 3);
+		graph.add_edge(a, b, 10);
+		graph.add_edge(b, c, 20);
+		graph.add_edge(a, c, 30);
+		graph.highlight_path(&[a, b]);
+		graph
+	}
+
+	#[test]
+	fn formats() {
+		let graph = graph();
+		assert_eq!(
+			graph.render(GraphFormat::Dot).unwrap(),
+			"digraph {\n\t0 [label=\"a\\n1\", color=red, penwidth=2]\n\t1 [label=\"\\\"b\\\"\\n2\", \
+			 color=red, penwidth=2]\n\t2 [label=\"c<\\n3\"]\n\t0 -> 1 [label=\"10\", color=red, \
+			 penwidth=2]\n\t1 -> 2 [label=\"20\"]\n\t0 -> 2 [label=\"30\"]\n}\n"
+		);
+		let graphml = graph.render(GraphFormat::Graphml).unwrap();
+		assert!(graphml.contains("<node id=\"n2\"><data key=\"label\">c&lt;</data>"));
+		assert!(graphml.contains(
+			"<edge source=\"n0\" target=\"n1\"><data key=\"weight\">10</data><data \
+			 key=\"highlight\">true</data></edge>"
+		));
+		let json: serde_json::Value =
+			serde_json::from_str(&graph.render(GraphFormat::Json).unwrap()).unwrap();
+		assert_eq!(json["nodes"][1]["label"], "\"b\"");
+		assert_eq!(json["edges"][2]["target"], 2);
+		assert_eq!(json["edges"][0]["highlight"], true);
+	}
+}
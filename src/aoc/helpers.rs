@@ -1,27 +1,42 @@
+pub use crate::aoc::parse_error::ParseError;
+#[cfg(feature = "cli")]
 use crate::Inputs;
 use anyhow::Context;
-use arc_swap::ArcSwapOption;
+use nom::error::VerboseError;
+use once_cell::sync::OnceCell;
 use std::borrow::Cow;
 use std::ffi::OsStr;
 use std::fmt::{Display, Formatter};
+#[cfg(feature = "cli")]
 use std::fs::File;
 use std::hash::{BuildHasher, Hasher};
-use std::io::BufRead;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::Arc;
+
+/// Where internal inputs are read from before falling back to the embedded ones, see
+/// [`set_inputs_dir`].
+#[cfg(feature = "cli")]
+static INPUTS_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Read the internal inputs from `<dir>/<year>/dayDD.input` when that file exists, rather than
+/// the inputs embedded when building.  Only the first call has any effect.
+#[cfg(feature = "cli")]
+pub fn set_inputs_dir(dir: PathBuf) {
+	let _ = INPUTS_DIR.set(dir);
+}
 
 #[derive(Debug)]
 pub struct DataFrom {
 	data: DataFromState,
-	cache: ArcSwapOption<Cow<'static, str>>,
+	cache: OnceCell<InputData>,
 }
 
 impl Clone for DataFrom {
 	fn clone(&self) -> Self {
 		DataFrom {
 			data: self.data.clone(),
-			cache: ArcSwapOption::default(),
+			cache: OnceCell::new(),
 		}
 	}
 }
@@ -30,84 +45,122 @@ impl From<&'static str> for DataFrom {
 	fn from(value: &'static str) -> Self {
 		DataFrom {
 			data: DataFromState::Static(Cow::Borrowed(value)),
-			cache: ArcSwapOption::default(),
+			cache: OnceCell::new(),
 		}
 	}
 }
 
+/// A valid input that a day can't solve, as it relies on something every real input happens to
+/// have.  Kept apart from other errors so that commands running many inputs can flag it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssumptionFailed(pub String);
+
+impl Display for AssumptionFailed {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "input breaks an assumption: {}", self.0)
+	}
+}
+
+impl std::error::Error for AssumptionFailed {}
+
 #[derive(Clone, Debug)]
 pub enum DataFromState {
-	Internal { year: u16, day: u8 },
+	Internal {
+		year: u16,
+		day: u8,
+	},
+	Example {
+		year: u16,
+		day: u8,
+		example: u8,
+	},
 	Static(Cow<'static, str>),
 	Stdin,
 	FilePath(PathBuf),
+	/// A file that is memory-mapped rather than read, for very large inputs.
+	Mmap(PathBuf),
+	/// A file that is decompressed as it is read.
+	Compressed(PathBuf, Compression),
 }
 
-impl DataFrom {
-	#[must_use]
-	pub fn internal(year: u16, day: u8) -> Self {
-		DataFrom {
-			data: DataFromState::Internal { year, day },
-			cache: ArcSwapOption::default(),
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+	Gzip,
+	Zstd,
+}
+
+impl Compression {
+	fn from_path(path: &Path) -> Option<Self> {
+		match path.extension()?.to_str()? {
+			"gz" => Some(Compression::Gzip),
+			"zst" | "zstd" => Some(Compression::Zstd),
+			_ => None,
 		}
 	}
-	pub fn preload(&self) -> anyhow::Result<()> {
-		let data = self.as_cow_str()?;
-		self.cache.store(Some(Arc::new(data)));
-		Ok(())
+}
+
+/// Input after normalization and UTF-8 validation, either in memory or still
+/// memory-mapped.
+#[derive(Debug)]
+enum InputData {
+	Bytes(Cow<'static, [u8]>),
+	#[cfg(feature = "cli")]
+	Mapped(memmap2::Mmap, Range<usize>),
+}
+
+impl InputData {
+	fn new(data: Cow<'static, [u8]>) -> anyhow::Result<Self> {
+		let data = normalize_input(data);
+		std::str::from_utf8(&data).context("input must be valid utf-8")?;
+		Ok(InputData::Bytes(data))
 	}
 
-	pub fn as_cow_str(&self) -> anyhow::Result<Cow<'static, str>> {
-		use std::io::Read;
-		if let Some(data) = &*self.cache.load() {
-			return Ok(Cow::clone(data));
+	#[cfg(feature = "cli")]
+	fn mapped(map: memmap2::Mmap) -> anyhow::Result<Self> {
+		let Some(range) = normalized_range(&map) else {
+			return InputData::new(Cow::Owned(normalized_copy(&map)));
+		};
+		std::str::from_utf8(&map[range.clone()]).context("input must be valid utf-8")?;
+		Ok(InputData::Mapped(map, range))
+	}
+
+	fn as_bytes(&self) -> &[u8] {
+		match self {
+			InputData::Bytes(data) => data,
+			#[cfg(feature = "cli")]
+			InputData::Mapped(map, range) => &map[range.clone()],
 		}
-		Ok(match &self.data {
-			DataFromState::Internal { year, day } => {
-				// let path = &format!("{year}/day{day}.input");
-				let y0 = (year / 1000) as u8 + b'0';
-				let y1 = ((year / 100) % 10) as u8 + b'0';
-				let y2 = ((year / 10) % 10) as u8 + b'0';
-				let y3 = (year % 10) as u8 + b'0';
-				let d0 = (day / 10) + b'0';
-				let d1 = (day % 10) + b'0';
-				let path = &[
-					y0, y1, y2, y3, b'/', b'd', b'a', b'y', d0, d1, b'.', b'i', b'n', b'p', b'u',
-					b't',
-				] as &[u8];
-				let path = unsafe { std::str::from_utf8_unchecked(path) };
-				let data = Inputs::get(path)
-					.with_context(|| format!("missing {}", &path))
-					.context("invalid internal input year and/or day")?;
-				Cow::Owned(
-					String::from_utf8(data.data.as_ref().to_vec())
-						.context("input must be valid utf-8")?,
-				)
-			}
-			DataFromState::Static(data) => data.clone(),
-			DataFromState::Stdin => {
-				let mut data = Vec::default();
-				std::io::stdin()
-					.read_to_end(&mut data)
-					.context("invalid read from stdin")?;
-				Cow::Owned(String::from_utf8(data).context("input must be valid utf-8")?)
+	}
+
+	fn as_str(&self) -> &str {
+		// SAFETY: both constructors validate the data as UTF-8.
+		unsafe { std::str::from_utf8_unchecked(self.as_bytes()) }
+	}
+
+	fn into_cow_str(self) -> Cow<'static, str> {
+		match self {
+			// SAFETY: both constructors validate the data as UTF-8.
+			InputData::Bytes(Cow::Borrowed(data)) => {
+				Cow::Borrowed(unsafe { std::str::from_utf8_unchecked(data) })
 			}
-			DataFromState::FilePath(path) => {
-				let data = std::fs::read_to_string(path)
-					.with_context(|| format!("invalid read from path: {path:?}"))?;
-				Cow::Owned(data)
+			InputData::Bytes(Cow::Owned(data)) => {
+				Cow::Owned(unsafe { String::from_utf8_unchecked(data) })
 			}
-		})
+			#[cfg(feature = "cli")]
+			data @ InputData::Mapped(..) => Cow::Owned(data.as_str().to_string()),
+		}
 	}
+}
 
-	pub fn as_cow_u8(&self) -> anyhow::Result<Cow<[u8]>> {
+impl DataFromState {
+	/// Reads the input and normalizes it, see [`normalize_input`], so every source
+	/// yields the same bytes for the same input.  Without the `cli` feature only inputs held in
+	/// memory can be read.
+	fn load(&self) -> anyhow::Result<InputData> {
+		#[cfg(feature = "cli")]
 		use std::io::Read;
-		if let Some(data) = &*self.cache.load() {
-			if let Cow::Borrowed(data) = Arc::as_ref(data) {
-				return Ok(Cow::Borrowed(data.as_bytes()));
-			}
-		}
-		Ok(match &self.data {
+		InputData::new(match self {
+			#[cfg(feature = "cli")]
 			DataFromState::Internal { year, day } => {
 				// let path = &format!("{year}/day{day}.input");
 				let y0 = (year / 1000) as u8 + b'0';
@@ -121,12 +174,31 @@ impl DataFrom {
 					b't',
 				] as &[u8];
 				let path = unsafe { std::str::from_utf8_unchecked(path) };
-				let data = Inputs::get(path)
-					.with_context(|| format!("missing {}", &path))
-					.context("invalid internal year day")?;
+				match INPUTS_DIR.get().map(|dir| dir.join(path)) {
+					Some(file) if file.is_file() => {
+						Cow::Owned(std::fs::read(&file).with_context(|| {
+							format!("invalid read from path: {}", file.display())
+						})?)
+					}
+					_ => {
+						let data = Inputs::get(path)
+							.with_context(|| format!("missing {}", &path))
+							.context("invalid internal input year and/or day")?;
+						data.data
+					}
+				}
+			}
+			#[cfg(feature = "cli")]
+			DataFromState::Example { year, day, example } => {
+				let path = example_path(*year, *day, *example, "input");
+				let data = Inputs::get(&path)
+					.with_context(|| format!("missing {path}"))
+					.context("invalid internal example year, day and/or number")?;
 				data.data
 			}
-			DataFromState::Static(data) => Cow::Borrowed(data.as_bytes()),
+			DataFromState::Static(Cow::Borrowed(data)) => Cow::Borrowed(data.as_bytes()),
+			DataFromState::Static(Cow::Owned(data)) => Cow::Owned(data.as_bytes().to_vec()),
+			#[cfg(feature = "cli")]
 			DataFromState::Stdin => {
 				let mut data = Vec::default();
 				std::io::stdin()
@@ -134,32 +206,533 @@ impl DataFrom {
 					.context("invalid read from stdin")?;
 				Cow::Owned(data)
 			}
+			#[cfg(feature = "cli")]
 			DataFromState::FilePath(path) => {
 				let data = std::fs::read(path)
-					.with_context(|| format!("invalid read from path: {path:?}"))?;
+					.with_context(|| format!("invalid read from path: {}", path.display()))?;
 				Cow::Owned(data)
 			}
+			#[cfg(feature = "cli")]
+			DataFromState::Mmap(path) => {
+				let file = File::open(path)
+					.with_context(|| format!("invalid read from path: {}", path.display()))?;
+				// SAFETY: like any memory-mapped file the input must not be modified while
+				// it is being solved.
+				let map = unsafe { memmap2::Mmap::map(&file) }
+					.with_context(|| format!("invalid memory map of path: {}", path.display()))?;
+				return InputData::mapped(map);
+			}
+			#[cfg(feature = "cli")]
+			DataFromState::Compressed(path, compression) => {
+				let file = File::open(path)
+					.with_context(|| format!("invalid read from path: {}", path.display()))?;
+				let mut data = Vec::default();
+				match compression {
+					Compression::Gzip => {
+						flate2::read::MultiGzDecoder::new(file).read_to_end(&mut data)
+					}
+					Compression::Zstd => zstd::stream::read::Decoder::new(file)
+						.and_then(|mut decoder| decoder.read_to_end(&mut data)),
+				}
+				.with_context(|| {
+					format!("invalid {compression:?} data in path: {}", path.display())
+				})?;
+				Cow::Owned(data)
+			}
+			#[cfg(not(feature = "cli"))]
+			external => anyhow::bail!("reading {external} needs the `cli` feature"),
+		})
+	}
+}
+
+#[cfg(feature = "cli")]
+fn example_path(year: u16, day: u8, example: u8, extension: &str) -> String {
+	format!("{year}/day{day:02}.example{example}.{extension}")
+}
+
+/// The part of the input without a byte order mark or trailing blank lines.
+fn content_range(data: &[u8]) -> Range<usize> {
+	let start = if data.starts_with(b"\xEF\xBB\xBF") {
+		3
+	} else {
+		0
+	};
+	let mut end = data.len();
+	while let Some(idx) = data[start..end].iter().rposition(|&b| b == b'\n') {
+		if data[start + idx + 1..end]
+			.iter()
+			.all(u8::is_ascii_whitespace)
+		{
+			end = start + idx;
+		} else {
+			break;
+		}
+	}
+	if data[start..end].iter().all(u8::is_ascii_whitespace) {
+		end = start;
+	}
+	start..end
+}
+
+/// The range of `data` that is already normalized, or `None` if it has to be copied
+/// by [`normalized_copy`] instead.
+fn normalized_range(data: &[u8]) -> Option<Range<usize>> {
+	let Range { start, end } = content_range(data);
+	if start == end {
+		Some(start..end)
+	} else if !data[start..end].contains(&b'\r') && data.get(end) == Some(&b'\n') {
+		Some(start..end + 1)
+	} else {
+		None
+	}
+}
+
+fn normalized_copy(data: &[u8]) -> Vec<u8> {
+	let content = &data[content_range(data)];
+	let mut normalized = Vec::with_capacity(content.len() + 1);
+	for (idx, &b) in content.iter().enumerate() {
+		if b != b'\r' || !matches!(content.get(idx + 1), None | Some(b'\n')) {
+			normalized.push(b);
+		}
+	}
+	if !normalized.is_empty() {
+		normalized.push(b'\n');
+	}
+	normalized
+}
+
+/// Normalizes raw input so that it:
+///
+/// * has no UTF-8 byte order mark,
+/// * uses `\n` line endings, with `\r\n` converted,
+/// * has no trailing blank lines,
+/// * ends with exactly one `\n`, unless it is empty.
+///
+/// Already normalized input is returned as-is without copying.
+fn normalize_input(data: Cow<'static, [u8]>) -> Cow<'static, [u8]> {
+	match (normalized_range(&data), data) {
+		(Some(range), Cow::Borrowed(data)) => Cow::Borrowed(&data[range]),
+		(Some(range), Cow::Owned(mut data)) => {
+			data.truncate(range.end);
+			data.drain(..range.start);
+			Cow::Owned(data)
+		}
+		(None, data) => Cow::Owned(normalized_copy(&data)),
+	}
+}
+
+impl DataFrom {
+	#[must_use]
+	pub fn internal(year: u16, day: u8) -> Self {
+		DataFrom {
+			data: DataFromState::Internal { year, day },
+			cache: OnceCell::new(),
+		}
+	}
+
+	/// An input held in memory that isn't `'static`, such as generated data.  Not a `From<String>`
+	/// impl as clap would prefer that over `FromStr` when parsing arguments.
+	#[must_use]
+	pub fn owned(data: String) -> Self {
+		DataFrom {
+			data: DataFromState::Static(Cow::Owned(data)),
+			cache: OnceCell::new(),
+		}
+	}
+
+	/// A file on disk, decompressed as it is read when its extension is that of a compression.
+	#[must_use]
+	pub fn file(path: PathBuf) -> Self {
+		DataFrom {
+			data: match Compression::from_path(&path) {
+				Some(compression) => DataFromState::Compressed(path, compression),
+				None => DataFromState::FilePath(path),
+			},
+			cache: OnceCell::new(),
+		}
+	}
+
+	/// One of the embedded puzzle examples, `inputs/YYYY/dayDD.exampleN.input`.
+	#[must_use]
+	pub fn example(year: u16, day: u8, example: u8) -> Self {
+		DataFrom {
+			data: DataFromState::Example { year, day, example },
+			cache: OnceCell::new(),
+		}
+	}
+
+	/// All the embedded examples of a day, in order, stopping at the first missing number.
+	#[cfg(feature = "cli")]
+	pub fn examples(year: u16, day: u8) -> impl Iterator<Item = Self> {
+		(1..=u8::MAX)
+			.take_while(move |&example| {
+				Inputs::get(&example_path(year, day, example, "input")).is_some()
+			})
+			.map(move |example| DataFrom::example(year, day, example))
+	}
+
+	/// The expected answers of an example input, from its `dayDD.exampleN.answer`
+	/// sidecar: the first line is the step 1 answer, everything after it the step 2
+	/// answer, which allows multi-line answers for step 2.
+	#[cfg(feature = "cli")]
+	pub fn expected_answers(&self) -> anyhow::Result<(String, String)> {
+		let DataFromState::Example { year, day, example } = self.data else {
+			anyhow::bail!("only example inputs have expected answers: {}", self.data);
+		};
+		let path = example_path(year, day, example, "answer");
+		let data = Inputs::get(&path).with_context(|| format!("missing {path}"))?;
+		let data = InputData::new(data.data)
+			.context("invalid answers")?
+			.into_cow_str();
+		let (step1, step2) = data.split_once('\n').unwrap_or((&data, ""));
+		Ok((step1.to_string(), step2.trim_end().to_string()))
+	}
+
+	pub fn preload(&self) -> anyhow::Result<()> {
+		self.as_str().map(|_| ())
+	}
+
+	fn load(&self) -> anyhow::Result<&InputData> {
+		self.cache
+			.get_or_try_init(|| self.data.load())
+			.with_context(|| format!("failed to load input: {}", self.data))
+	}
+
+	/// The whole input, read once and then served from the cache on every later call.
+	pub fn as_str(&self) -> anyhow::Result<&str> {
+		Ok(self.load()?.as_str())
+	}
+
+	pub fn as_bytes(&self) -> anyhow::Result<&[u8]> {
+		Ok(self.load()?.as_bytes())
+	}
+
+	pub fn as_cow_str(&self) -> anyhow::Result<Cow<'static, str>> {
+		if let Some(data) = self.cache.get() {
+			if let InputData::Bytes(Cow::Borrowed(bytes)) = data {
+				// SAFETY: both constructors validate the data as UTF-8.
+				return Ok(Cow::Borrowed(unsafe {
+					std::str::from_utf8_unchecked(bytes)
+				}));
+			}
+			return Ok(Cow::Owned(data.as_str().to_string()));
+		}
+		Ok(self.data.load()?.into_cow_str())
+	}
+
+	/// The whole input as bytes, borrowed from the cache or memory map.
+	pub fn as_cow_u8(&self) -> anyhow::Result<Cow<'_, [u8]>> {
+		Ok(Cow::Borrowed(self.as_bytes()?))
+	}
+
+	/// A [`ParseError`] pointing at `found`, which should be a slice of [`DataFrom::as_str`].
+	pub fn parse_error(&self, found: &str, expected: impl Display) -> ParseError {
+		ParseError::new(
+			self.source_name(),
+			self.as_str().unwrap_or_default(),
+			found,
+			expected,
+		)
+	}
+
+	/// A [`ParseError`] for a nom parser that was run over [`DataFrom::as_str`].
+	pub fn nom_error(&self, err: nom::Err<VerboseError<&str>>) -> ParseError {
+		ParseError::from_nom(self.source_name(), self.as_str().unwrap_or_default(), err)
+	}
+
+	fn source_name(&self) -> Cow<'_, str> {
+		match &self.data {
+			DataFromState::Static(_) => Cow::Borrowed("<static>"),
+			data => Cow::Owned(data.to_string()),
+		}
+	}
+
+	/// Iterate over the lines of the input, without their line terminators.
+	///
+	/// Trimming and skipping empty lines are opt-in via [`Lines::trimmed`] and
+	/// [`Lines::non_empty`].
+	pub fn lines(&self) -> anyhow::Result<Lines<'_>> {
+		Ok(Lines::new(self.as_str()?))
+	}
+
+	/// Same as [`DataFrom::lines`] but yields the raw bytes of each line.
+	pub fn bytes_lines(&self) -> anyhow::Result<BytesLines<'_>> {
+		Ok(BytesLines::new(self.as_bytes()?))
+	}
+
+	/// Iterate over the blank-line separated blocks of the input, each block
+	/// without its surrounding blank lines or its final line terminator.
+	pub fn blocks(&self) -> anyhow::Result<Blocks<'_>> {
+		Ok(Blocks::new(self.as_str()?))
+	}
+
+	/// Run a day's `solve` over the input, pointing any [`ParseError`] it fails with at where the
+	/// input came from.
+	pub fn solve<T>(&self, solve: impl FnOnce(&str) -> anyhow::Result<T>) -> anyhow::Result<T> {
+		solve(self.as_str()?).map_err(|mut err| {
+			if let Some(parse_error) = err.downcast_mut::<ParseError>() {
+				parse_error.source = self.source_name().into_owned();
+			}
+			err
 		})
 	}
 }
 
+/// `input` normalized the same way as every [`DataFrom`] is when it is read, borrowed when it
+/// already is.
+#[must_use]
+pub fn normalize(input: &str) -> Cow<'_, str> {
+	match normalized_range(input.as_bytes()) {
+		Some(range) => Cow::Borrowed(&input[range]),
+		// Only ASCII bytes and the byte order mark are dropped, so this is still valid UTF-8
+		None => {
+			Cow::Owned(String::from_utf8_lossy(&normalized_copy(input.as_bytes())).into_owned())
+		}
+	}
+}
+
+/// Iterator over the lines of a [`DataFrom`], see [`DataFrom::bytes_lines`].
+#[derive(Clone, Debug)]
+pub struct BytesLines<'a> {
+	rest: &'a [u8],
+	line: usize,
+	trim: bool,
+	skip_empty: bool,
+}
+
+impl<'a> BytesLines<'a> {
+	/// Iterate over the lines of `data`, as [`DataFrom::bytes_lines`] does for a whole input.
+	#[must_use]
+	pub fn new(data: &'a [u8]) -> Self {
+		BytesLines {
+			rest: data,
+			line: 0,
+			trim: false,
+			skip_empty: false,
+		}
+	}
+
+	/// Trim leading and trailing ASCII whitespace from every line.
+	#[must_use]
+	pub fn trimmed(mut self) -> Self {
+		self.trim = true;
+		self
+	}
+
+	/// Skip lines that are empty, after trimming if that is enabled.
+	#[must_use]
+	pub fn non_empty(mut self) -> Self {
+		self.skip_empty = true;
+		self
+	}
+
+	/// The 1-based line number of the line most recently returned.
+	#[must_use]
+	pub fn line_number(&self) -> usize {
+		self.line
+	}
+
+	/// Map every line through `f`, adding the line number and content to any error.
+	pub fn try_map<R, F>(
+		mut self,
+		mut f: F,
+	) -> impl Iterator<Item = anyhow::Result<R>> + use<'a, R, F>
+	where
+		F: FnMut(&'a [u8]) -> anyhow::Result<R>,
+	{
+		std::iter::from_fn(move || {
+			let line = self.next()?;
+			Some(f(line).with_context(|| {
+				format!(
+					"Failed parsing line {}: {:?}",
+					self.line,
+					String::from_utf8_lossy(line)
+				)
+			}))
+		})
+	}
+}
+
+impl<'a> Iterator for BytesLines<'a> {
+	type Item = &'a [u8];
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if self.rest.is_empty() {
+				return None;
+			}
+			let mut line = if let Some(idx) = self.rest.iter().position(|&b| b == b'\n') {
+				let (line, rest) = self.rest.split_at(idx);
+				self.rest = &rest[1..];
+				line
+			} else {
+				std::mem::take(&mut self.rest)
+			};
+			self.line += 1;
+			if self.trim {
+				line = line.trim_ascii();
+			}
+			if !self.skip_empty || !line.is_empty() {
+				return Some(line);
+			}
+		}
+	}
+}
+
+/// Iterator over the lines of a [`DataFrom`], see [`DataFrom::lines`].
+#[derive(Clone, Debug)]
+pub struct Lines<'a>(BytesLines<'a>);
+
+impl<'a> Lines<'a> {
+	/// Iterate over the lines of `data`, as [`DataFrom::lines`] does for a whole input.
+	#[must_use]
+	pub fn new(data: &'a str) -> Self {
+		Lines(BytesLines::new(data.as_bytes()))
+	}
+
+	/// Trim leading and trailing ASCII whitespace from every line.
+	#[must_use]
+	pub fn trimmed(self) -> Self {
+		Lines(self.0.trimmed())
+	}
+
+	/// Skip lines that are empty, after trimming if that is enabled.
+	#[must_use]
+	pub fn non_empty(self) -> Self {
+		Lines(self.0.non_empty())
+	}
+
+	/// The 1-based line number of the line most recently returned.
+	#[must_use]
+	pub fn line_number(&self) -> usize {
+		self.0.line_number()
+	}
+
+	/// Map every line through `f`, adding the line number and content to any error.
+	pub fn try_map<R, F>(
+		mut self,
+		mut f: F,
+	) -> impl Iterator<Item = anyhow::Result<R>> + use<'a, R, F>
+	where
+		F: FnMut(&'a str) -> anyhow::Result<R>,
+	{
+		std::iter::from_fn(move || {
+			let line = self.next()?;
+			Some(f(line).with_context(|| format!("Failed parsing line {}: {line}", self.0.line)))
+		})
+	}
+}
+
+impl<'a> Iterator for Lines<'a> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		// SAFETY: the bytes come from a `&str` and are only ever split on or
+		// trimmed of ASCII bytes, so every line is still valid UTF-8.
+		self.0
+			.next()
+			.map(|line| unsafe { std::str::from_utf8_unchecked(line) })
+	}
+}
+
+/// Iterator over the blank-line separated blocks of a [`DataFrom`], see [`DataFrom::blocks`].
+#[derive(Clone, Debug)]
+pub struct Blocks<'a> {
+	data: &'a str,
+	pos: usize,
+	block: usize,
+}
+
+impl<'a> Blocks<'a> {
+	/// Iterate over the blocks of `data`, as [`DataFrom::blocks`] does for a whole input.
+	#[must_use]
+	pub fn new(data: &'a str) -> Self {
+		Blocks {
+			data,
+			pos: 0,
+			block: 0,
+		}
+	}
+
+	/// The 1-based index of the block most recently returned.
+	#[must_use]
+	pub fn block_number(&self) -> usize {
+		self.block
+	}
+
+	/// Map every block through `f`, adding the block index and content to any error.
+	pub fn try_map<R, F>(
+		mut self,
+		mut f: F,
+	) -> impl Iterator<Item = anyhow::Result<R>> + use<'a, R, F>
+	where
+		F: FnMut(&'a str) -> anyhow::Result<R>,
+	{
+		std::iter::from_fn(move || {
+			let block = self.next()?;
+			Some(f(block).with_context(|| format!("Failed parsing block {}:\n{block}", self.block)))
+		})
+	}
+}
+
+impl<'a> Iterator for Blocks<'a> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut start = None;
+		let mut end = self.pos;
+		while self.pos < self.data.len() {
+			let rest = &self.data[self.pos..];
+			let len = rest.find('\n').map_or(rest.len(), |idx| idx + 1);
+			let line = &rest[..len];
+			let line_start = self.pos;
+			self.pos += len;
+			if line.trim_ascii().is_empty() {
+				if start.is_some() {
+					break;
+				}
+			} else {
+				start.get_or_insert(line_start);
+				end = line_start + line.trim_end_matches('\n').len();
+			}
+		}
+		let start = start?;
+		self.block += 1;
+		Some(&self.data[start..end])
+	}
+}
+
 impl Display for DataFromState {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		match self {
 			DataFromState::Internal { year, day } => f.write_fmt(format_args!(":{year}:{day}")),
+			DataFromState::Example { year, day, example } => {
+				f.write_fmt(format_args!(":{year}:{day}:example{example}"))
+			}
 			DataFromState::Static(data) => f.write_str(data),
 			DataFromState::Stdin => f.write_str("-"),
-			DataFromState::FilePath(filepath) => {
-				if let Some(p) = filepath.to_str() {
-					f.write_str(p)
-				} else {
-					panic!("Internal file paths should always be UTF-8: {filepath:?}")
-				}
+			DataFromState::FilePath(filepath) | DataFromState::Compressed(filepath, _) => {
+				fmt_path(filepath, f)
+			}
+			DataFromState::Mmap(filepath) => {
+				f.write_str("mmap:")?;
+				fmt_path(filepath, f)
 			}
 		}
 	}
 }
 
+fn fmt_path(filepath: &Path, f: &mut Formatter<'_>) -> std::fmt::Result {
+	if let Some(p) = filepath.to_str() {
+		f.write_str(p)
+	} else {
+		panic!(
+			"Internal file paths should always be UTF-8: {}",
+			filepath.display()
+		)
+	}
+}
+
 impl Display for DataFrom {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		self.data.fmt(f)
@@ -172,7 +745,7 @@ impl FromStr for DataFrom {
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		Ok(DataFrom {
 			data: DataFromState::from_str(s)?,
-			cache: ArcSwapOption::default(),
+			cache: OnceCell::new(),
 		})
 	}
 }
@@ -186,12 +759,26 @@ impl FromStr for DataFromState {
 		} else if let Some(s) = s.strip_prefix(':') {
 			let (year, day) = s.split_once(':').context("invalid :year:day order")?;
 			let year = year.parse().with_context(|| "invalid :year:day order")?;
+			if let Some((day, example)) = day.split_once(':') {
+				let day = day.parse().context("invalid :year:day:exampleN order")?;
+				let example = example
+					.strip_prefix("example")
+					.and_then(|example| example.parse().ok())
+					.context("invalid :year:day:exampleN order")?;
+				return Ok(DataFromState::Example { year, day, example });
+			}
 			let day = day.parse().with_context(|| "invalid :year:day order")?;
 			Ok(DataFromState::Internal { year, day })
 		} else if s.contains('\n') {
 			Ok(DataFromState::Static(Cow::Owned(s.to_string())))
+		} else if let Some(s) = s.strip_prefix("mmap:") {
+			Ok(DataFromState::Mmap(PathBuf::from(s)))
 		} else {
-			Ok(DataFromState::FilePath(PathBuf::from(s)))
+			let path = PathBuf::from(s);
+			Ok(match Compression::from_path(&path) {
+				Some(compression) => DataFromState::Compressed(path, compression),
+				None => DataFromState::FilePath(path),
+			})
 		}
 	}
 }
@@ -206,187 +793,77 @@ impl From<&OsStr> for DataFromState {
 	}
 }
 
+/// Callback form of [`Lines::new`].
 pub fn process_lines_of_file(
-	data: &DataFrom,
+	data: &str,
 	mut cb: impl FnMut(&str) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
-	use std::io::BufReader;
-	if let Some(data) = &*data.cache.load() {
-		for line in data.lines() {
-			cb(line).with_context(|| format!("Failed parsing line: {data}"))?;
-		}
-		return Ok(());
-	}
-	match &data.data {
-		DataFromState::Internal { year, day } => {
-			// let path = format!("{year}/day{day}.input");
-			let y0 = (year / 1000) as u8 + b'0';
-			let y1 = ((year / 100) % 10) as u8 + b'0';
-			let y2 = ((year / 10) % 10) as u8 + b'0';
-			let y3 = (year % 10) as u8 + b'0';
-			let d0 = (day / 10) + b'0';
-			let d1 = (day % 10) + b'0';
-			let path = &[
-				y0, y1, y2, y3, b'/', b'd', b'a', b'y', d0, d1, b'.', b'i', b'n', b'p', b'u', b't',
-			] as &[u8];
-			let path = unsafe { std::str::from_utf8_unchecked(path) };
-			let data = Inputs::get(path).with_context(|| format!("missing {path}"))?;
-			for line in std::str::from_utf8(data.data.as_ref())?.lines() {
-				cb(line).with_context(|| format!("Failed parsing line: {line}"))?;
-			}
-		}
-		DataFromState::Static(data) => {
-			for line in data.lines() {
-				cb(line).with_context(|| format!("Failed parsing line: {line}"))?;
-			}
-		}
-		DataFromState::Stdin => {
-			let stdin = std::io::stdin();
-			let mut handle = stdin.lock();
-			let mut line = String::with_capacity(64);
-			while handle.read_line(&mut line)? > 0 {
-				cb(&line).with_context(|| format!("Failed parsing line: {line}"))?;
-				line.clear();
-			}
-		}
-		DataFromState::FilePath(filepath) => {
-			let mut data = BufReader::new(File::open(filepath)?);
-			let mut line = String::with_capacity(64);
-			while data.read_line(&mut line)? > 0 {
-				cb(&line).with_context(|| format!("Failed parsing line: {line}"))?;
-				line.clear();
-			}
-		}
-	}
-	Ok(())
+	Lines::new(data).try_map(&mut cb).collect()
 }
 
+/// Callback form of [`BytesLines::new`].
 pub fn process_lines_of_file_bytes(
-	data: &DataFrom,
+	data: &str,
 	mut cb: impl FnMut(&[u8]) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
-	match &data.data {
-		DataFromState::Internal { year, day } => {
-			// let path = format!("{year}/day{day}.input");
-			let y0 = (year / 1000) as u8 + b'0';
-			let y1 = ((year / 100) % 10) as u8 + b'0';
-			let y2 = ((year / 10) % 10) as u8 + b'0';
-			let y3 = (year % 10) as u8 + b'0';
-			let d0 = (day / 10) + b'0';
-			let d1 = (day % 10) + b'0';
-			let path = &[
-				y0, y1, y2, y3, b'/', b'd', b'a', b'y', d0, d1, b'.', b'i', b'n', b'p', b'u', b't',
-			] as &[u8];
-			let path = unsafe { std::str::from_utf8_unchecked(path) };
-			let data = Inputs::get(path).with_context(|| format!("missing `{path}`"))?;
-			for line in data.data.as_ref().split(|&b| b == b'\n') {
-				cb(line).with_context(|| {
-					format!("Failed parsing line: {:?}", std::str::from_utf8(line))
-				})?;
-			}
-		}
-		DataFromState::Static(data) => {
-			for line in data.as_bytes().split(|&b| b == b'\n') {
-				cb(line).with_context(|| {
-					format!("Failed parsing line: {:?}", std::str::from_utf8(line))
-				})?;
-			}
-		}
-		DataFromState::Stdin => {
-			let stdin = std::io::stdin();
-			let mut handle = stdin.lock();
-			let mut line = Vec::with_capacity(64);
-			while handle.read_until(b'\n', &mut line)? > 0 {
-				cb(&line).with_context(|| {
-					format!("Failed parsing line: {:?}", std::str::from_utf8(&line))
-				})?;
-				line.clear();
-			}
-		}
-		DataFromState::FilePath(filepath) => {
-			let mut data = std::io::BufReader::new(File::open(filepath)?);
-			let mut line = Vec::with_capacity(64);
-			while data.read_until(b'\n', &mut line)? > 0 {
-				cb(&line).with_context(|| {
-					format!("Failed parsing line: {:?}", std::str::from_utf8(&line))
-				})?;
-				line.clear();
-			}
-		}
-	}
-	Ok(())
+	BytesLines::new(data.as_bytes()).try_map(&mut cb).collect()
 }
 
+/// Callback form of [`Lines::new`] with [`Lines::trimmed`].
 pub fn process_trimmed_lines_of_file(
-	data: &DataFrom,
+	data: &str,
 	mut cb: impl FnMut(&str) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
-	process_lines_of_file(data, |line| {
-		cb(line.trim())?;
-		Ok(())
-	})
+	Lines::new(data).trimmed().try_map(&mut cb).collect()
 }
 
+/// Callback form of [`BytesLines::new`] with [`BytesLines::trimmed`].
 pub fn process_trimmed_lines_of_file_bytes(
-	data: &DataFrom,
+	data: &str,
 	mut cb: impl FnMut(&[u8]) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
-	process_lines_of_file_bytes(data, |mut line| {
-		while !line.is_empty() {
-			if line[0].is_ascii_whitespace() {
-				line = &line[1..];
-			} else {
-				break;
-			}
-		}
-		while !line.is_empty() {
-			if line[line.len() - 1].is_ascii_whitespace() {
-				line = &line[..line.len() - 1];
-			} else {
-				break;
-			}
-		}
-		cb(line)?;
-		Ok(())
-	})
+	BytesLines::new(data.as_bytes())
+		.trimmed()
+		.try_map(&mut cb)
+		.collect()
 }
 
+/// Callback form of [`Lines::new`] with [`Lines::trimmed`] and [`Lines::non_empty`].
 pub fn process_trimmed_nonempty_lines_of_file(
-	data: &DataFrom,
+	data: &str,
 	mut cb: impl FnMut(&str) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
-	process_trimmed_lines_of_file(data, |line| {
-		if !line.is_empty() {
-			cb(line)?;
-		}
-		Ok(())
-	})
+	Lines::new(data)
+		.trimmed()
+		.non_empty()
+		.try_map(&mut cb)
+		.collect()
 }
 
+/// Callback form of [`BytesLines::new`] with [`BytesLines::trimmed`] and
+/// [`BytesLines::non_empty`].
 pub fn process_trimmed_nonempty_lines_of_file_bytes(
-	data: &DataFrom,
+	data: &str,
 	mut cb: impl FnMut(&[u8]) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
-	process_trimmed_lines_of_file_bytes(data, |line| {
-		if !line.is_empty() {
-			cb(line)?;
-		}
-		Ok(())
-	})
+	BytesLines::new(data.as_bytes())
+		.trimmed()
+		.non_empty()
+		.try_map(&mut cb)
+		.collect()
 }
 
 pub fn flat_map_trimmed_nonempty_lines_of_file<
 	R: IntoIterator,
 	F: FnMut(&str) -> anyhow::Result<R>,
 >(
-	data: &DataFrom,
-	mut cb: F,
+	data: &str,
+	cb: F,
 ) -> anyhow::Result<Vec<<R as IntoIterator>::Item>> {
 	let mut results = Vec::with_capacity(8192);
-	process_trimmed_nonempty_lines_of_file(data, |line| {
-		results.extend(cb(line)?);
-		Ok(())
-	})?;
+	for items in Lines::new(data).trimmed().non_empty().try_map(cb) {
+		results.extend(items?);
+	}
 	Ok(results)
 }
 
@@ -394,43 +871,40 @@ pub fn flat_map_trimmed_nonempty_lines_of_file_bytes<
 	R: IntoIterator,
 	F: FnMut(&[u8]) -> anyhow::Result<R>,
 >(
-	data: &DataFrom,
-	mut cb: F,
+	data: &str,
+	cb: F,
 ) -> anyhow::Result<Vec<<R as IntoIterator>::Item>> {
 	let mut results = Vec::with_capacity(8192);
-	process_trimmed_nonempty_lines_of_file_bytes(data, |line| {
-		results.extend(cb(line)?);
-		Ok(())
-	})?;
+	for items in BytesLines::new(data.as_bytes())
+		.trimmed()
+		.non_empty()
+		.try_map(cb)
+	{
+		results.extend(items?);
+	}
 	Ok(results)
 }
 
 pub fn map_trimmed_nonempty_lines_of_file<R, F: FnMut(&str) -> anyhow::Result<R>>(
-	data: &DataFrom,
-	mut cb: F,
+	data: &str,
+	cb: F,
 ) -> anyhow::Result<Vec<R>> {
-	let mut results = Vec::with_capacity(8192);
-	process_trimmed_nonempty_lines_of_file(data, |line| {
-		results.push(cb(line)?);
-		Ok(())
-	})?;
-	Ok(results)
+	Lines::new(data).trimmed().non_empty().try_map(cb).collect()
 }
 
 pub fn map_trimmed_nonempty_lines_of_file_bytes<R, F: FnMut(&[u8]) -> anyhow::Result<R>>(
-	data: &DataFrom,
-	mut cb: F,
+	data: &str,
+	cb: F,
 ) -> anyhow::Result<Vec<R>> {
-	let mut results = Vec::with_capacity(8192);
-	process_trimmed_nonempty_lines_of_file_bytes(data, |line| {
-		results.push(cb(line)?);
-		Ok(())
-	})?;
-	Ok(results)
+	BytesLines::new(data.as_bytes())
+		.trimmed()
+		.non_empty()
+		.try_map(cb)
+		.collect()
 }
 
 pub fn fold_trimmed_nonempty_lines_of_file<R, F: FnMut(R, &str) -> anyhow::Result<R>>(
-	data: &DataFrom,
+	data: &str,
 	acc: R,
 	mut cb: F,
 ) -> anyhow::Result<R> {
@@ -446,7 +920,7 @@ pub fn fold_trimmed_nonempty_lines_of_file<R, F: FnMut(R, &str) -> anyhow::Resul
 }
 
 pub fn fold_trimmed_nonempty_lines_of_file_bytes<R, F: FnMut(R, &[u8]) -> anyhow::Result<R>>(
-	data: &DataFrom,
+	data: &str,
 	acc: R,
 	mut cb: F,
 ) -> anyhow::Result<R> {
@@ -462,6 +936,8 @@ pub fn fold_trimmed_nonempty_lines_of_file_bytes<R, F: FnMut(R, &[u8]) -> anyhow
 }
 
 #[cfg(test)]
+#[must_use]
+#[allow(clippy::missing_panics_doc)]
 pub fn gen_internal_input(year: u16, day: u8) -> DataFrom {
 	let input = DataFrom::internal(year, day);
 	input
@@ -471,15 +947,57 @@ pub fn gen_internal_input(year: u16, day: u8) -> DataFrom {
 }
 
 #[cfg(test)]
-pub const EMPTY_TUI_AOC_APP: crate::AocApp = crate::AocApp {
+pub static EMPTY_TUI_AOC_APP: crate::AocApp = crate::AocApp {
 	verbose: 0,
 	hide_scores: true,
+	no_cache: false,
+	format: crate::OutputFormat::Text,
+	trace: Vec::new(),
+	trace_file: None,
+	inputs_dir: None,
+	artifacts_dir: std::path::PathBuf::new(),
+	artifacts: std::sync::Mutex::new(Vec::new()),
 	command: crate::AocAppCommand::TUI,
 };
 
 #[cfg(test)]
 #[macro_export]
 macro_rules! run_basic_tests {
+	// Days can have options besides the input, so build them as if run with only the input
+	(@day $Day:tt, $input:expr) => {{
+		let mut day = <$Day as clap::Parser>::parse_from(["test"]);
+		day.input = $input;
+		day
+	}};
+	(super::$day:tt, examples: ($year:literal, $day_num:literal) $(, $($rest:tt)*)?) => {
+		use super::$day as Day;
+		$crate::run_basic_tests!(Day, examples: ($year, $day_num) $(, $($rest)*)?);
+	};
+	($Day:tt, examples: ($year:literal, $day_num:literal) $(, $($rest:tt)*)?) => {
+		#[test]
+		fn example_files() {
+			let mut found = false;
+			for input in $crate::aoc::helpers::DataFrom::examples($year, $day_num) {
+				found = true;
+				let expected = match input.expected_answers() {
+					Ok(expected) => expected,
+					Err(err) => panic!("Missing answers for input {input}\n{err:?}"),
+				};
+				let day = $crate::run_basic_tests!(@day $Day, input);
+				match day.run(&$crate::aoc::helpers::EMPTY_TUI_AOC_APP) {
+					Ok((score1, score2)) => assert_eq!(
+						(score1.to_string(), score2.to_string()),
+						expected,
+						"Test failed for input {}",
+						day.input
+					),
+					Err(err) => panic!("Test failed for input {}\n{err:?}", day.input),
+				}
+			}
+			assert!(found, "No example inputs for {} day {}", $year, $day_num);
+		}
+		$($crate::run_basic_tests!($Day, $($rest)*);)?
+	};
 	(super::$day:tt, $($data_name:ident : $test_data:expr,)*) => {
 		use super::$day as Day;
 		$crate::run_basic_tests!(Day, $($data_name : $test_data,)*);
@@ -488,9 +1006,8 @@ macro_rules! run_basic_tests {
 		$(
 			#[test] fn $data_name() {
 				let (input, expected) = $test_data;
-				let day = $Day {
-					input: $crate::aoc::helpers::DataFrom::from(input),
-				};
+				let day =
+					$crate::run_basic_tests!(@day $Day, $crate::aoc::helpers::DataFrom::from(input));
 				match day.run(&$crate::aoc::helpers::EMPTY_TUI_AOC_APP) {
 					Ok(result) => assert_eq!(result, expected),
 					Err(err) => panic!("Test failed for input:\n{input}\n{err:?}"),
@@ -506,9 +1023,8 @@ macro_rules! run_basic_tests {
 		#[test]
 		fn example_data() {
 			for (input, expected) in $test_data {
-				let day = $Day {
-					input: $crate::aoc::helpers::DataFrom::from(input),
-				};
+				let day =
+					$crate::run_basic_tests!(@day $Day, $crate::aoc::helpers::DataFrom::from(input));
 				match day.run(&$crate::aoc::helpers::EMPTY_TUI_AOC_APP) {
 					Ok(result) => assert_eq!(result, expected),
 					Err(err) => panic!("Test failed for input:\n{input}\n{err:?}"),
@@ -561,3 +1077,150 @@ impl Hasher for IdentityHasher {
 
 pub type IdentityHashSet<T> = std::collections::HashSet<T, IdentityHasher>;
 pub type IdentityHashMap<T, V> = std::collections::HashMap<T, V, IdentityHasher>;
+
+#[cfg(test)]
+mod tests {
+	use super::{normalize_input, DataFrom, InputData};
+	use std::borrow::Cow;
+	use std::str::FromStr;
+
+	/// The same input as saved by various editors and platforms.
+	fn messy_variants(data: &str) -> [String; 4] {
+		let crlf = data.replace('\n', "\r\n");
+		[
+			format!("{data}\n\n  \n"),
+			crlf.clone(),
+			format!("\u{feff}{crlf}\r\n\r\n"),
+			data.trim_end().to_string(),
+		]
+	}
+
+	#[test]
+	fn examples() {
+		let example = DataFrom::from_str(":2023:12:example2").unwrap();
+		assert_eq!(example.to_string(), ":2023:12:example2");
+		assert_eq!(
+			example.expected_answers().unwrap(),
+			("21".to_string(), "525152".to_string())
+		);
+		assert_eq!(DataFrom::examples(2023, 12).count(), 2);
+		assert!(DataFrom::from_str(":2023:12:sample1").is_err());
+		assert!(DataFrom::internal(2023, 12).expected_answers().is_err());
+	}
+
+	#[test]
+	fn normalize() {
+		let normalized = |data: &'static [u8]| normalize_input(Cow::Borrowed(data));
+		assert!(matches!(normalized(b"a\nb\n"), Cow::Borrowed(b"a\nb\n")));
+		assert!(matches!(
+			normalized(b"a\nb\n\n \n"),
+			Cow::Borrowed(b"a\nb\n")
+		));
+		assert!(matches!(
+			normalized(b"\xEF\xBB\xBFa\n"),
+			Cow::Borrowed(b"a\n")
+		));
+		assert_eq!(normalized(b"a\r\nb\r\n\r\n").as_ref(), b"a\nb\n");
+		assert_eq!(normalized(b"a\rb\r").as_ref(), b"a\rb\n");
+		assert_eq!(normalized(b" a\nb ").as_ref(), b" a\nb \n");
+		assert_eq!(normalized(b"\n\n \r\n").as_ref(), b"");
+		assert_eq!(
+			normalize_input(Cow::Owned(b"\xEF\xBB\xBFa\n\n".to_vec())).as_ref(),
+			b"a\n"
+		);
+	}
+
+	#[test]
+	fn static_sources() {
+		let expected = DataFrom::from("1\n2\n\n3\n");
+		for variant in messy_variants("1\n2\n\n3") {
+			let input = DataFrom::from_str(&variant).unwrap();
+			assert_eq!(input.as_str().unwrap(), expected.as_str().unwrap());
+			assert_eq!(
+				input.lines().unwrap().collect::<Vec<_>>(),
+				expected.lines().unwrap().collect::<Vec<_>>()
+			);
+		}
+	}
+
+	#[test]
+	fn internal_and_file_sources() {
+		use std::io::Write;
+		let internal = DataFrom::internal(2022, 1);
+		let expected = internal.as_str().unwrap();
+		let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		for (idx, variant) in messy_variants(expected).into_iter().enumerate() {
+			let path = dir.join(format!("{idx}.input"));
+			std::fs::write(&path, &variant).unwrap();
+			let mut gzip =
+				flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+			gzip.write_all(variant.as_bytes()).unwrap();
+			std::fs::write(path.with_extension("gz"), gzip.finish().unwrap()).unwrap();
+			std::fs::write(
+				path.with_extension("zst"),
+				zstd::encode_all(variant.as_bytes(), 0).unwrap(),
+			)
+			.unwrap();
+
+			let path = path.to_str().unwrap();
+			for source in [
+				path.to_string(),
+				format!("mmap:{path}"),
+				path.replace(".input", ".gz"),
+				path.replace(".input", ".zst"),
+			] {
+				let input = DataFrom::from_str(&source).unwrap();
+				assert_eq!(input.to_string(), source);
+				assert_eq!(input.as_cow_str().unwrap(), expected, "{source}");
+				assert_eq!(input.as_str().unwrap(), expected, "{source}");
+				assert_eq!(input.as_cow_u8().unwrap().as_ref(), expected.as_bytes());
+				assert_eq!(input.as_cow_str().unwrap(), expected, "{source}");
+			}
+		}
+		let input = DataFrom::from_str(&format!("mmap:{}/0.input", dir.display())).unwrap();
+		input.preload().unwrap();
+		assert!(matches!(input.cache.get(), Some(InputData::Mapped(..))));
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn lines() {
+		let input = DataFrom::from("  a \n\nb\r\n c\n");
+		assert_eq!(
+			input.lines().unwrap().collect::<Vec<_>>(),
+			["  a ", "", "b", " c"]
+		);
+		assert_eq!(
+			input
+				.lines()
+				.unwrap()
+				.trimmed()
+				.non_empty()
+				.collect::<Vec<_>>(),
+			["a", "b", "c"]
+		);
+		assert_eq!(
+			input.bytes_lines().unwrap().trimmed().collect::<Vec<_>>(),
+			[&b"a"[..], b"", b"b", b"c"]
+		);
+	}
+
+	#[test]
+	fn blocks() {
+		let input = DataFrom::from("\n1\n2\n\n \n3\r\n\n");
+		assert_eq!(input.blocks().unwrap().collect::<Vec<_>>(), ["1\n2", "3"]);
+	}
+
+	#[test]
+	fn try_map_context() {
+		let input = DataFrom::from("1\n2\nx\n");
+		let err = input
+			.lines()
+			.unwrap()
+			.try_map(|line| Ok(line.parse::<u8>()?))
+			.collect::<anyhow::Result<Vec<_>>>()
+			.unwrap_err();
+		assert_eq!(err.to_string(), "Failed parsing line 3: x");
+	}
+}
@@ -1,69 +1,115 @@
+#[cfg(feature = "cli")]
+pub mod answer_cache;
+#[cfg(feature = "cli")]
+pub mod batch;
+#[cfg(feature = "cli")]
+pub mod bench;
+pub mod check_input;
+#[cfg(feature = "cli")]
+pub mod compare_impls;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod generate;
+pub mod graph_export;
 pub mod helpers;
+pub mod parse_error;
+pub mod progress;
+pub mod registry;
+#[cfg(feature = "cli")]
+pub mod serve;
+pub mod solve;
+pub mod visualize;
+#[cfg(feature = "year2015")]
 pub mod year2015;
+#[cfg(feature = "year2016")]
 pub mod year2016;
+#[cfg(feature = "year2017")]
 pub mod year2017;
+#[cfg(feature = "year2018")]
 pub mod year2018;
+#[cfg(feature = "year2019")]
 pub mod year2019;
+#[cfg(feature = "year2020")]
 pub mod year2020;
+#[cfg(feature = "year2021")]
 pub mod year2021;
+#[cfg(feature = "year2022")]
 pub mod year2022;
+#[cfg(feature = "year2023")]
 pub mod year2023;
 
-use crate::AocApp;
+#[cfg(feature = "cli")]
+use crate::aoc::helpers::DataFrom;
+#[cfg(feature = "cli")]
+use crate::{AocApp, OutputFormat};
 use clap::Parser;
+#[cfg(feature = "cli")]
+use std::fmt::Display;
+#[cfg(feature = "cli")]
+use std::time::Instant;
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Parser)]
 pub enum AocYear {
 	/// Advent of Code 2015
+	#[cfg(feature = "year2015")]
 	#[clap(name = "2015")]
 	Year2015 {
 		#[clap(subcommand)]
 		day: year2015::Year2015,
 	},
 	/// Advent of Code 2016
+	#[cfg(feature = "year2016")]
 	#[clap(name = "2016")]
 	Year2016 {
 		#[clap(subcommand)]
 		day: year2016::Year2016,
 	},
 	/// Advent of Code 2017
+	#[cfg(feature = "year2017")]
 	#[clap(name = "2017")]
 	Year2017 {
 		#[clap(subcommand)]
 		day: year2017::Year2017,
 	},
 	/// Advent of Code 2018
+	#[cfg(feature = "year2018")]
 	#[clap(name = "2018")]
 	Year2018 {
 		#[clap(subcommand)]
 		day: year2018::Year2018,
 	},
 	/// Advent of Code 2019
+	#[cfg(feature = "year2019")]
 	#[clap(name = "2019")]
 	Year2019 {
 		#[clap(subcommand)]
 		day: year2019::Year2019,
 	},
 	/// Advent of Code 2020
+	#[cfg(feature = "year2020")]
 	#[clap(name = "2020")]
 	Year2020 {
 		#[clap(subcommand)]
 		day: year2020::Year2020,
 	},
 	/// Advent of Code 2021
+	#[cfg(feature = "year2021")]
 	#[clap(name = "2021")]
 	Year2021 {
 		#[clap(subcommand)]
 		day: year2021::Year2021,
 	},
 	/// Advent of Code 2022
+	#[cfg(feature = "year2022")]
 	#[clap(name = "2022")]
 	Year2022 {
 		#[clap(subcommand)]
 		day: year2022::Year2022,
 	},
 	/// Advent of Code 2023
+	#[cfg(feature = "year2023")]
 	#[clap(name = "2023")]
 	Year2023 {
 		#[clap(subcommand)]
@@ -71,56 +117,175 @@ pub enum AocYear {
 	},
 }
 
+#[cfg(feature = "cli")]
 impl AocYear {
+	// Without any year enabled there are no days to give the app to
+	#[cfg_attr(
+		not(any(
+			feature = "year2015",
+			feature = "year2016",
+			feature = "year2017",
+			feature = "year2018",
+			feature = "year2019",
+			feature = "year2020",
+			feature = "year2021",
+			feature = "year2022",
+			feature = "year2023"
+		)),
+		allow(unused_variables)
+	)]
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		match self {
-			AocYear::Year2015 { day } => day.run(app),
-			AocYear::Year2016 { day } => day.run(app),
-			AocYear::Year2017 { day } => day.run(app),
-			AocYear::Year2018 { day } => day.run(app),
-			AocYear::Year2019 { day } => day.run(app),
-			AocYear::Year2020 { day } => day.run(app),
-			AocYear::Year2021 { day } => day.run(app),
-			AocYear::Year2022 { day } => day.run(app),
-			AocYear::Year2023 { day } => day.run(app),
+		match *self {
+			#[cfg(feature = "year2015")]
+			AocYear::Year2015 { ref day } => day.run(app),
+			#[cfg(feature = "year2016")]
+			AocYear::Year2016 { ref day } => day.run(app),
+			#[cfg(feature = "year2017")]
+			AocYear::Year2017 { ref day } => day.run(app),
+			#[cfg(feature = "year2018")]
+			AocYear::Year2018 { ref day } => day.run(app),
+			#[cfg(feature = "year2019")]
+			AocYear::Year2019 { ref day } => day.run(app),
+			#[cfg(feature = "year2020")]
+			AocYear::Year2020 { ref day } => day.run(app),
+			#[cfg(feature = "year2021")]
+			AocYear::Year2021 { ref day } => day.run(app),
+			#[cfg(feature = "year2022")]
+			AocYear::Year2022 { ref day } => day.run(app),
+			#[cfg(feature = "year2023")]
+			AocYear::Year2023 { ref day } => day.run(app),
 		}
 	}
 
+	// Without any year enabled there are no days to give the app to
+	#[cfg_attr(
+		not(any(
+			feature = "year2015",
+			feature = "year2016",
+			feature = "year2017",
+			feature = "year2018",
+			feature = "year2019",
+			feature = "year2020",
+			feature = "year2021",
+			feature = "year2022",
+			feature = "year2023"
+		)),
+		allow(unused_variables)
+	)]
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
+		#[cfg(feature = "year2015")]
 		year2015::Year2015::run_all(app)?;
+		#[cfg(feature = "year2016")]
 		year2016::Year2016::run_all(app)?;
+		#[cfg(feature = "year2017")]
 		year2017::Year2017::run_all(app)?;
+		#[cfg(feature = "year2018")]
 		year2018::Year2018::run_all(app)?;
+		#[cfg(feature = "year2019")]
 		year2019::Year2019::run_all(app)?;
+		#[cfg(feature = "year2020")]
 		year2020::Year2020::run_all(app)?;
+		#[cfg(feature = "year2021")]
 		year2021::Year2021::run_all(app)?;
+		#[cfg(feature = "year2022")]
 		year2022::Year2022::run_all(app)?;
+		#[cfg(feature = "year2023")]
 		year2023::Year2023::run_all(app)?;
 		Ok(())
 	}
 }
 
+/// Run a day and report its answers.  Given the source of the day and the options it runs with, see
+/// [`answer_cache::options`], the answers are taken from the answer cache instead when none of the
+/// day, its options and its input have changed since they were cached, unless the cache is turned
+/// off with `--no-cache`.  With `-vv` the days write and print more as they run, so they always
+/// run then.
+#[cfg(feature = "cli")]
+pub fn run_day<S1: Display, S2: Display>(
+	app: &AocApp,
+	year: &str,
+	day: &str,
+	input: &DataFrom,
+	version: Option<(&str, &str)>,
+	run: impl FnOnce() -> anyhow::Result<(S1, S2)>,
+) -> anyhow::Result<()> {
+	let text = app.format == OutputFormat::Text;
+	if text {
+		println!("### {year} - {day}");
+	}
+	input.preload()?;
+	let numbers = year
+		.strip_prefix("Year")
+		.and_then(|year| year.parse().ok())
+		.zip(day.strip_prefix("Day").and_then(|day| day.parse().ok()));
+	let key = match (version, numbers) {
+		(Some((source, options)), Some((year, day))) if !app.no_cache && app.verbose < 2 => Some(
+			answer_cache::Key::new(year, day, source, options, input.as_bytes()?),
+		),
+		_ => None,
+	};
+	let cached = key.as_ref().and_then(answer_cache::get);
+	let hit = cached.is_some();
+	let start = Instant::now();
+	let (score1, score2) = if let Some(answers) = cached {
+		answers
+	} else {
+		let (score1, score2) = anyhow::Context::context(run(), "failed to run day")?;
+		(score1.to_string(), score2.to_string())
+	};
+	let taken = start.elapsed();
+	let artifacts = app.take_artifacts();
+	if text {
+		if !app.hide_scores {
+			println!("Step 1: {score1}");
+			println!("Step 2: {score2}");
+		}
+		for artifact in &artifacts {
+			println!("Wrote: {}", artifact.display());
+		}
+		if app.verbose >= 1 {
+			if hit {
+				println!("_{day} Cached_");
+			} else {
+				println!("_{day} Time Taken: {taken:?}_");
+			}
+		}
+	} else {
+		let (year, day) = numbers.unzip();
+		println!(
+			"{}",
+			serde_json::json!({
+				"year": year,
+				"day": day,
+				"part1": (!app.hide_scores).then_some(&score1),
+				"part2": (!app.hide_scores).then_some(&score2),
+				"nanos": (!hit).then_some(taken.as_nanos()),
+				"cached": hit,
+				"artifacts": artifacts,
+			})
+		);
+	}
+	if let Some(key) = key.filter(|_| !hit) {
+		if let Err(err) = answer_cache::put(&key, (score1, score2)) {
+			tracing::warn!("failed to cache the answers of {year} {day}: {err:#}");
+		}
+	}
+	Ok(())
+}
+
 #[macro_export]
 macro_rules! run_days {
 	($year:ident, $self:ident, $app:ident, [$($day:ident),* $(,)*]) => {
 		match $self {
 			$year::RunAll => $year::run_all($app),
-			$(Self::$day(day) => {
-				println!("### {} - {}", stringify!($year), stringify!($day));
-				day.input.preload()?;
-				let start = std::time::Instant::now();
-				let res = day.run($app);
-				let taken = start.elapsed();
-				let (score1, score2) = anyhow::Context::context(res, "failed to run day")?;
-				if !$app.hide_scores {
-					println!("Step 1: {score1}");
-					println!("Step 2: {score2}");
-				}
-				if $app.verbose >= 1 {
-					println!("_{} Time Taken: {:?}_", stringify!($day), taken);
-				}
-				Ok(())
-			})*
+			$(Self::$day(day) => $crate::aoc::run_day(
+				$app,
+				stringify!($year),
+				stringify!($day),
+				&day.input,
+				None,
+				|| day.run($app),
+			),)*
 		}
 	}
 }
@@ -128,15 +293,42 @@ macro_rules! run_days {
 #[macro_export]
 macro_rules! run_all_days {
 	($self:ident, $app:ident, [$($day:ident),* $(,)*]) => {{
-		{
-			let _ = $app;
+		let text = $app.format == $crate::OutputFormat::Text;
+		if text {
+			println!("## {}", stringify!($self));
 		}
-		println!("## {}", stringify!($self));
 		let start = std::time::Instant::now();
 		$({
-			$self::$day(clap::Parser::parse_from(&[] as &[&str])).run($app)?;
+			// With the options of the day from the config, as there are none from the command line
+			let mut command = $crate::aoc::config::day_command(
+				&stringify!($self)["Year".len()..],
+				&stringify!($day)["Day".len()..],
+				<$day as clap::CommandFactory>::command(),
+			)?;
+			let matches = command.try_get_matches_from_mut(&[] as &[&str])?;
+			let day = <$day as clap::FromArgMatches>::from_arg_matches(&matches)?;
+			// The source of the day versions its cached answers, and a day writing files has to run
+			// to write them
+			let source = paste::paste!(include_str!(concat!(
+				env!("CARGO_MANIFEST_DIR"),
+				"/src/aoc/",
+				stringify!([<$self:lower>]),
+				"/",
+				stringify!([<$day:lower>]),
+				".rs"
+			)));
+			let options = $crate::aoc::answer_cache::options(&command, &matches);
+			$crate::aoc::run_day(
+				$app,
+				stringify!($self),
+				stringify!($day),
+				&day.input,
+				(!$crate::aoc::answer_cache::writes_artifacts(&matches))
+					.then_some((source, options.as_str())),
+				|| day.run($app),
+			)?;
 		})*
-		if $app.verbose >= 1 {
+		if text && $app.verbose >= 1 {
 			println!("_{} Time Taken: {:?}_", stringify!($self), start.elapsed());
 		}
 		Ok(())
@@ -0,0 +1,164 @@
+use nom::error::{VerboseError, VerboseErrorKind};
+use std::fmt::{Display, Formatter};
+
+/// A parse failure that points at the offending text of the input, rendered with
+/// the line it is on and carets under the text:
+///
+/// ```text
+/// error: expected `Valve `, found "Vavle"
+///  --> ./day16.input:3:1
+///   |
+/// 3 | Vavle BB has flow rate=13; tunnels lead to valves CC, AA
+///   | ^^^^^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+	/// Where the input came from, such as a file path or `:2022:16`.
+	pub source: String,
+	/// 1-based line number, 0 when the location is unknown.
+	pub line: usize,
+	/// 1-based column in characters, 0 when the location is unknown.
+	pub column: usize,
+	pub line_text: String,
+	pub expected: String,
+	/// The offending text, empty at the end of a line or of the input.
+	pub found: String,
+}
+
+impl ParseError {
+	/// Build an error for `found`, which should be a sub-slice of `input`; if it is
+	/// not then the first occurrence of it in `input` is used instead.
+	pub fn new(source: impl Display, input: &str, found: &str, expected: impl Display) -> Self {
+		let offset = (found.as_ptr() as usize)
+			.checked_sub(input.as_ptr() as usize)
+			.filter(|&offset| offset + found.len() <= input.len())
+			.or_else(|| input.find(found));
+		let Some(offset) = offset else {
+			return ParseError {
+				source: source.to_string(),
+				line: 0,
+				column: 0,
+				line_text: String::new(),
+				expected: expected.to_string(),
+				found: found.to_string(),
+			};
+		};
+		let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+		let line_end = input[offset..]
+			.find('\n')
+			.map_or(input.len(), |idx| offset + idx);
+		let found_end = (offset + found.len()).min(line_end);
+		ParseError {
+			source: source.to_string(),
+			line: bytecount::count(&input.as_bytes()[..offset], b'\n') + 1,
+			column: input[line_start..offset].chars().count() + 1,
+			line_text: input[line_start..line_end].to_string(),
+			expected: expected.to_string(),
+			found: input[offset..found_end].to_string(),
+		}
+	}
+
+	/// Build an error from a failed nom parser run over the whole of `input`, using the
+	/// innermost error and any contexts it was wrapped in.
+	pub fn from_nom(source: impl Display, input: &str, err: nom::Err<VerboseError<&str>>) -> Self {
+		let errors = match err {
+			nom::Err::Incomplete(_) => {
+				return ParseError::new(source, input, &input[input.len()..], "more input")
+			}
+			nom::Err::Error(err) | nom::Err::Failure(err) => err.errors,
+		};
+		let Some((rest, kind)) = errors.first() else {
+			return ParseError::new(source, input, "", "valid input");
+		};
+		let mut expected = match kind {
+			VerboseErrorKind::Char(c) => format!("`{c}`"),
+			VerboseErrorKind::Context(context) => (*context).to_string(),
+			VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+		};
+		for (_, kind) in &errors[1..] {
+			if let VerboseErrorKind::Context(context) = kind {
+				expected.push_str(" in ");
+				expected.push_str(context);
+			}
+		}
+		let found = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+		ParseError::new(source, input, found, expected)
+	}
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "error: expected {}, found ", self.expected)?;
+		if !self.found.is_empty() {
+			write!(f, "{:?}", self.found)?;
+		} else if self.line == 0 {
+			f.write_str("nothing")?;
+		} else {
+			f.write_str("end of line")?;
+		}
+		if self.line == 0 {
+			return write!(f, "\n --> {}", self.source);
+		}
+		let gutter = " ".repeat(self.line.to_string().len());
+		writeln!(
+			f,
+			"\n{gutter}--> {}:{}:{}",
+			self.source, self.line, self.column
+		)?;
+		writeln!(f, "{gutter} |")?;
+		writeln!(f, "{} | {}", self.line, self.line_text)?;
+		let padding: String = self
+			.line_text
+			.chars()
+			.take(self.column - 1)
+			.map(|c| if c == '\t' { '\t' } else { ' ' })
+			.collect();
+		let carets = "^".repeat(self.found.chars().count().max(1));
+		write!(f, "{gutter} | {padding}{carets}")
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+	use super::ParseError;
+	use nom::character::complete::{char, digit1};
+	use nom::error::context;
+	use nom::sequence::preceded;
+
+	#[test]
+	fn render_manual() {
+		let input = "Valve AA has flow rate=0\n\tValve BB hsa flow rate=13\n";
+		let line = input.lines().nth(1).unwrap();
+		let found = &line[10..13];
+		let err = ParseError::new("test.input", input, found, "`has`");
+		assert_eq!((err.line, err.column), (2, 11));
+		assert_eq!(
+			err.to_string(),
+			"error: expected `has`, found \"hsa\"\n --> test.input:2:11\n  |\n2 | \tValve BB hsa \
+			 flow rate=13\n  | \t         ^^^"
+		);
+	}
+
+	#[test]
+	fn render_nom() {
+		let input = "1\n2\nx=3\n";
+		let parser = |i| context("value", preceded(char('x'), digit1))(i);
+		let err = parser(&input[4..]).unwrap_err();
+		let err = ParseError::from_nom(":2022:13", input, err);
+		assert_eq!((err.line, err.column), (3, 2));
+		assert_eq!(err.expected, "digit in value");
+		assert_eq!(err.found, "=");
+	}
+
+	#[test]
+	fn unknown_location() {
+		let err = ParseError::new("-", "abc", "xyz", "a number");
+		assert_eq!(err.line, 0);
+		assert_eq!(
+			err.to_string(),
+			"error: expected a number, found \"xyz\"\n --> -"
+		);
+	}
+}
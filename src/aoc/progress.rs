@@ -0,0 +1,168 @@
+use crate::{AocApp, OutputFormat};
+use std::cell::{Cell, RefCell};
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// Nothing is drawn for days that finish sooner than this, so quick days don't flicker.
+const DELAY: Duration = Duration::from_millis(250);
+/// How often to redraw.
+const INTERVAL: Duration = Duration::from_millis(100);
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const BAR_WIDTH: usize = 30;
+
+/// How far a long running day has got, such as rounds completed or search nodes expanded, shown
+/// on stderr as a progress bar when the total is known or as a spinner when it isn't.  It is only
+/// shown on a terminal, and not with `--format json`, otherwise reporting into it costs next to
+/// nothing.  The line is cleared once it is dropped.
+pub struct Progress {
+	bar: Option<Bar>,
+}
+
+struct Bar {
+	what: &'static str,
+	total: Option<u64>,
+	done: Cell<u64>,
+	/// Only look at the clock once `done` reaches this, so that reporting in a hot loop is cheap
+	next_check: Cell<u64>,
+	step: Cell<u64>,
+	started: Instant,
+	last_check: Cell<Instant>,
+	last_draw: Cell<Option<Instant>>,
+	frame: Cell<usize>,
+	line: RefCell<String>,
+}
+
+impl Progress {
+	/// Report progress in `what`, such as `"rounds"`, out of `total` if it is known.
+	#[must_use]
+	pub fn new(app: &AocApp, what: &'static str, total: Option<u64>) -> Self {
+		if app.format != OutputFormat::Text || !std::io::stderr().is_terminal() {
+			return Self::hidden();
+		}
+		let now = Instant::now();
+		Progress {
+			bar: Some(Bar {
+				what,
+				total,
+				done: Cell::new(0),
+				next_check: Cell::new(1),
+				step: Cell::new(1),
+				started: now,
+				last_check: Cell::new(now),
+				last_draw: Cell::new(None),
+				frame: Cell::new(0),
+				line: RefCell::new(String::new()),
+			}),
+		}
+	}
+
+	/// Progress that is never shown, for running a day's internals on their own, such as in tests.
+	#[must_use]
+	pub fn hidden() -> Self {
+		Progress { bar: None }
+	}
+
+	/// Add `n` to what has been done.
+	#[inline]
+	pub fn inc(&self, n: u64) {
+		if let Some(bar) = &self.bar {
+			bar.set(bar.done.get() + n);
+		}
+	}
+
+	/// Set how much has been done.
+	#[inline]
+	pub fn set(&self, done: u64) {
+		if let Some(bar) = &self.bar {
+			bar.set(done);
+		}
+	}
+}
+
+impl Bar {
+	#[inline]
+	fn set(&self, done: u64) {
+		self.done.set(done);
+		if done >= self.next_check.get() {
+			self.check();
+		}
+	}
+
+	/// Look at the clock, drawing if it is time to, and adjust how many more reports to wait for
+	/// before looking again so that it is roughly every millisecond.
+	#[cold]
+	fn check(&self) {
+		let now = Instant::now();
+		let step = if now - self.last_check.get() < Duration::from_millis(1) {
+			self.step.get().saturating_mul(2)
+		} else {
+			(self.step.get() / 2).max(1)
+		};
+		self.step.set(step);
+		self.next_check.set(self.done.get().saturating_add(step));
+		self.last_check.set(now);
+		let due = match self.last_draw.get() {
+			Some(last) => now - last >= INTERVAL,
+			None => now - self.started >= DELAY,
+		};
+		if due {
+			self.last_draw.set(Some(now));
+			self.draw(now);
+		}
+	}
+
+	#[allow(
+		clippy::cast_precision_loss,
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss
+	)]
+	fn draw(&self, now: Instant) {
+		use std::fmt::Write as _;
+		let done = self.done.get();
+		let elapsed = now - self.started;
+		let mut line = self.line.borrow_mut();
+		line.clear();
+		match self.total {
+			Some(total) if total > 0 => {
+				let fraction = (done as f64 / total as f64).min(1.0);
+				let filled = (fraction * BAR_WIDTH as f64) as usize;
+				let _ = write!(
+					line,
+					"[{}{}] {done}/{total} {} {:>3.0}% {elapsed:.1?}",
+					"=".repeat(filled),
+					" ".repeat(BAR_WIDTH - filled),
+					self.what,
+					fraction * 100.0,
+				);
+			}
+			_ => {
+				let frame = self.frame.get();
+				self.frame.set(frame + 1);
+				let _ = write!(
+					line,
+					"{} {done} {} {elapsed:.1?}",
+					SPINNER[frame % SPINNER.len()],
+					self.what
+				);
+			}
+		}
+		let mut stderr = std::io::stderr().lock();
+		let _ = write!(stderr, "\r\x1b[2K{line}");
+		let _ = stderr.flush();
+	}
+}
+
+impl Drop for Progress {
+	fn drop(&mut self) {
+		if let Some(Bar {
+			last_draw: drawn, ..
+		}) = &self.bar
+		{
+			if drawn.get().is_some() {
+				let mut stderr = std::io::stderr().lock();
+				let _ = write!(stderr, "\r\x1b[2K");
+				let _ = stderr.flush();
+			}
+		}
+	}
+}
@@ -0,0 +1,182 @@
+use crate::aoc::helpers::DataFrom;
+use crate::AocApp;
+
+/// A built day, run as many times as needed, giving its answers as text.
+pub type Runner = Box<dyn Fn(&AocApp) -> anyhow::Result<(String, String)>>;
+
+/// A day that commands running many days can build with any input, with its other options left
+/// at their defaults.
+pub struct RegisteredDay {
+	pub year: u16,
+	pub day: u8,
+	/// The title of the day's puzzle, such as `Trebuchet?!`.
+	pub title: &'static str,
+	build: fn(DataFrom, Option<&str>) -> anyhow::Result<Runner>,
+	solve: fn(&str) -> anyhow::Result<(String, String)>,
+	command: fn() -> clap::Command,
+}
+
+impl RegisteredDay {
+	/// Build the day with `input`, which is loaded right away so that running it only solves it.
+	pub fn build(&self, input: DataFrom) -> anyhow::Result<Runner> {
+		(self.build)(input, None)
+	}
+
+	/// Build the day to run the named one of its [`implementations`](Self::implementations).
+	pub fn build_impl(&self, input: DataFrom, implementation: &str) -> anyhow::Result<Runner> {
+		(self.build)(input, Some(implementation))
+	}
+
+	/// Solve both parts for `input` with the day's own `solve`, which takes no options and needs
+	/// no app.
+	pub fn solve(&self, input: &str) -> anyhow::Result<(String, String)> {
+		(self.solve)(input)
+	}
+
+	/// The names of the implementations the day can choose between with `--impl`, the default
+	/// first, or nothing for a day with only the one.
+	#[must_use]
+	pub fn implementations(&self) -> Vec<String> {
+		let command = (self.command)();
+		let Some(arg) = command
+			.get_arguments()
+			.find(|arg| arg.get_long() == Some("impl"))
+		else {
+			return Vec::new();
+		};
+		let default = arg.get_default_values().first().cloned();
+		let mut names: Vec<String> = arg
+			.get_possible_values()
+			.iter()
+			.map(|value| value.get_name().to_string())
+			.collect();
+		names.sort_by_key(|name| {
+			default
+				.as_ref()
+				.is_none_or(|default| default != name.as_str())
+		});
+		names
+	}
+}
+
+// Without any year enabled there are no days to register
+#[cfg_attr(
+	not(any(
+		feature = "year2015",
+		feature = "year2016",
+		feature = "year2017",
+		feature = "year2018",
+		feature = "year2019",
+		feature = "year2020",
+		feature = "year2021",
+		feature = "year2022",
+		feature = "year2023"
+	)),
+	allow(unused_macros)
+)]
+macro_rules! register_days {
+	($registry:ident, $year:literal, $module:ident, [$($day:ident),* $(,)*]) => {
+		$($registry.push(RegisteredDay {
+			year: $year,
+			day: stringify!($day)[3..].parse().expect("days are named `Day<N>`"),
+			title: super::$module::$day::TITLE,
+			build: |input, implementation| {
+				let mut day = match implementation {
+					Some(implementation) => <super::$module::$day as clap::Parser>::try_parse_from(
+						["registry", "--impl", implementation],
+					)?,
+					None => <super::$module::$day as clap::Parser>::parse_from(["registry"]),
+				};
+				day.input = input;
+				day.input.preload()?;
+				Ok(Box::new(move |app| {
+					day.run(app)
+						.map(|(score1, score2)| (score1.to_string(), score2.to_string()))
+				}))
+			},
+			solve: |input| {
+				super::$module::$day::solve(input)
+					.map(|(score1, score2)| (score1.to_string(), score2.to_string()))
+			},
+			command: <super::$module::$day as clap::CommandFactory>::command,
+		});)*
+	};
+}
+
+/// Every day of the enabled years, in order.
+#[allow(unused_mut)]
+#[must_use]
+pub fn registered_days() -> Vec<RegisteredDay> {
+	let mut days = Vec::with_capacity(64);
+	#[cfg(feature = "year2015")]
+	crate::year2015_days!(register_days!(days, 2015, year2015,));
+	#[cfg(feature = "year2016")]
+	crate::year2016_days!(register_days!(days, 2016, year2016,));
+	#[cfg(feature = "year2017")]
+	crate::year2017_days!(register_days!(days, 2017, year2017,));
+	#[cfg(feature = "year2018")]
+	crate::year2018_days!(register_days!(days, 2018, year2018,));
+	#[cfg(feature = "year2019")]
+	crate::year2019_days!(register_days!(days, 2019, year2019,));
+	#[cfg(feature = "year2020")]
+	crate::year2020_days!(register_days!(days, 2020, year2020,));
+	#[cfg(feature = "year2021")]
+	crate::year2021_days!(register_days!(days, 2021, year2021,));
+	#[cfg(feature = "year2022")]
+	crate::year2022_days!(register_days!(days, 2022, year2022,));
+	#[cfg(feature = "year2023")]
+	crate::year2023_days!(register_days!(days, 2023, year2023,));
+	days
+}
+
+/// The registered day, if its year is enabled and it exists.
+#[must_use]
+pub fn registered_day(year: u16, day: u8) -> Option<RegisteredDay> {
+	registered_days()
+		.into_iter()
+		.find(|r| r.year == year && r.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{registered_day, registered_days};
+	use crate::aoc::helpers::{DataFrom, EMPTY_TUI_AOC_APP};
+
+	#[test]
+	fn implementations() {
+		let day16 = registered_day(2022, 16).unwrap();
+		assert_eq!(day16.implementations(), ["permutations", "search"]);
+		assert!(registered_day(2022, 1)
+			.unwrap()
+			.implementations()
+			.is_empty());
+		let run = day16
+			.build_impl(DataFrom::example(2022, 16, 1), "permutations")
+			.unwrap();
+		assert_eq!(
+			run(&EMPTY_TUI_AOC_APP).unwrap(),
+			("1651".to_string(), "1707".to_string())
+		);
+		assert!(day16
+			.build_impl(DataFrom::internal(2022, 16), "missing")
+			.is_err());
+	}
+
+	#[test]
+	fn titles_match_the_help() {
+		let command = <crate::aoc::AocYear as clap::CommandFactory>::command();
+		for registered in registered_days() {
+			let about = command
+				.find_subcommand(registered.year.to_string())
+				.and_then(|year| year.find_subcommand(registered.day.to_string()))
+				.and_then(clap::Command::get_about)
+				.map(ToString::to_string)
+				.unwrap_or_default();
+			assert!(
+				about.ends_with(registered.title),
+				"{about:?} doesn't end with {:?}",
+				registered.title
+			);
+		}
+	}
+}
@@ -0,0 +1,375 @@
+use crate::aoc::batch::{run_input, Status};
+use crate::aoc::helpers::DataFrom;
+use crate::aoc::registry::registered_day;
+use crate::aoc::solve::days;
+use crate::{AocApp, AocAppCommand, OutputFormat};
+use anyhow::{bail, Context};
+use clap::Args;
+use serde_json::{json, Value};
+use std::io::Read;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::time::timeout_at;
+
+/// The most a request line and headers can take.
+const MAX_HEAD: usize = 16 * 1024;
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+	/// The port to listen on
+	#[clap(short, long, default_value_t = 8080)]
+	pub port: u16,
+	/// The address to listen on, only this machine by default
+	#[clap(long, default_value = "127.0.0.1")]
+	pub host: std::net::IpAddr,
+	/// Seconds a request has to be sent and solved in, including waiting for a free job
+	#[clap(long, default_value_t = 30)]
+	pub timeout: u64,
+	/// The largest input accepted, in bytes
+	#[clap(long, default_value_t = 16 * 1024 * 1024)]
+	pub max_input: usize,
+	/// How many inputs to solve at once, defaults to the number of CPUs
+	#[clap(short, long)]
+	pub jobs: Option<usize>,
+}
+
+/// What is shared by every request.
+struct Server {
+	/// This program, run once per input so that a solve which takes too long can be killed
+	solver: PathBuf,
+	timeout: Duration,
+	max_input: usize,
+	jobs: Arc<Semaphore>,
+	started: Instant,
+	solving: AtomicU64,
+	solved: AtomicU64,
+	failed: AtomicU64,
+	timed_out: AtomicU64,
+}
+
+/// A JSON response.
+struct Response {
+	status: u16,
+	body: Value,
+}
+
+impl Response {
+	fn ok(body: Value) -> Self {
+		Response { status: 200, body }
+	}
+
+	fn error(status: u16, error: impl Into<String>) -> Self {
+		Response {
+			status,
+			body: json!({ "error": error.into() }),
+		}
+	}
+
+	fn reason(&self) -> &'static str {
+		match self.status {
+			200 => "OK",
+			400 => "Bad Request",
+			404 => "Not Found",
+			405 => "Method Not Allowed",
+			408 => "Request Timeout",
+			411 => "Length Required",
+			413 => "Payload Too Large",
+			422 => "Unprocessable Entity",
+			500 => "Internal Server Error",
+			504 => "Gateway Timeout",
+			_ => "",
+		}
+	}
+
+	async fn write(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+		let body = self.body.to_string();
+		let head = format!(
+			"HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+			self.status,
+			self.reason(),
+			body.len()
+		);
+		stream.write_all(head.as_bytes()).await?;
+		stream.write_all(body.as_bytes()).await?;
+		stream.shutdown().await
+	}
+}
+
+/// A request with its body read.
+struct Request {
+	method: String,
+	path: String,
+	body: Vec<u8>,
+}
+
+pub fn run(args: &ServeArgs) -> anyhow::Result<()> {
+	let runtime = tokio::runtime::Runtime::new().context("failed to start the async runtime")?;
+	runtime.block_on(async {
+		let addr = SocketAddr::new(args.host, args.port);
+		let listener = TcpListener::bind(addr)
+			.await
+			.with_context(|| format!("failed to listen on {addr}"))?;
+		println!("Listening on http://{}", listener.local_addr()?);
+		serve(listener, args).await
+	})
+}
+
+/// Answer requests on `listener` until it fails.
+pub async fn serve(listener: TcpListener, args: &ServeArgs) -> anyhow::Result<()> {
+	let jobs = args
+		.jobs
+		.or_else(|| std::thread::available_parallelism().ok().map(Into::into))
+		.unwrap_or(1)
+		.max(1);
+	let solver = std::env::current_exe().context("failed to find this program to solve with")?;
+	let server = Arc::new(Server {
+		solver,
+		timeout: Duration::from_secs(args.timeout),
+		max_input: args.max_input,
+		jobs: Arc::new(Semaphore::new(jobs)),
+		started: Instant::now(),
+		solving: AtomicU64::new(0),
+		solved: AtomicU64::new(0),
+		failed: AtomicU64::new(0),
+		timed_out: AtomicU64::new(0),
+	});
+	loop {
+		let (stream, _) = listener.accept().await.context("failed to accept")?;
+		let server = server.clone();
+		tokio::spawn(async move {
+			if let Err(err) = server.connection(stream).await {
+				tracing::debug!("failed to answer a request: {err:#}");
+			}
+		});
+	}
+}
+
+impl Server {
+	async fn connection(&self, mut stream: TcpStream) -> anyhow::Result<()> {
+		let deadline = tokio::time::Instant::now() + self.timeout;
+		let response = match timeout_at(deadline, self.read(&mut stream)).await {
+			Err(_) => Response::error(408, "the request wasn't sent in time"),
+			Ok(Err(response)) => response,
+			Ok(Ok(request)) => self.route(request, deadline).await,
+		};
+		response.write(&mut stream).await?;
+		Ok(())
+	}
+
+	/// Read one request, or the response saying what is wrong with it.
+	async fn read(&self, stream: &mut TcpStream) -> Result<Request, Response> {
+		let mut data = Vec::new();
+		let head_end = loop {
+			if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+				break end;
+			}
+			if data.len() > MAX_HEAD {
+				return Err(Response::error(400, "the request headers are too large"));
+			}
+			data.reserve(4096);
+			if let Ok(0) | Err(_) = stream.read_buf(&mut data).await {
+				return Err(Response::error(400, "incomplete request"));
+			}
+		};
+		let head = std::str::from_utf8(&data[..head_end])
+			.map_err(|_| Response::error(400, "the request headers aren't UTF-8"))?;
+		let mut lines = head.split("\r\n");
+		let mut request_line = lines.next().unwrap_or_default().split(' ');
+		let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+			return Err(Response::error(400, "invalid request line"));
+		};
+		let mut length = None;
+		let mut expect_continue = false;
+		for line in lines {
+			let Some((name, value)) = line.split_once(':') else {
+				return Err(Response::error(400, "invalid header"));
+			};
+			let value = value.trim();
+			if name.eq_ignore_ascii_case("content-length") {
+				length = Some(
+					value
+						.parse::<usize>()
+						.map_err(|_| Response::error(400, "invalid Content-Length"))?,
+				);
+			} else if name.eq_ignore_ascii_case("transfer-encoding") {
+				return Err(Response::error(411, "send the input with a Content-Length"));
+			} else if name.eq_ignore_ascii_case("expect") {
+				expect_continue = value.eq_ignore_ascii_case("100-continue");
+			}
+		}
+		let length = length.unwrap_or(0);
+		if length > self.max_input {
+			return Err(Response::error(
+				413,
+				format!("inputs can be at most {} bytes", self.max_input),
+			));
+		}
+		let mut body = data[head_end + 4..].to_vec();
+		if expect_continue && body.len() < length {
+			let _ = stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").await;
+		}
+		body.reserve(length.saturating_sub(body.len()));
+		while body.len() < length {
+			if let Ok(0) | Err(_) = stream.read_buf(&mut body).await {
+				return Err(Response::error(400, "incomplete request body"));
+			}
+		}
+		body.truncate(length);
+		Ok(Request {
+			method: method.to_string(),
+			path: path.to_string(),
+			body,
+		})
+	}
+
+	async fn route(&self, request: Request, deadline: tokio::time::Instant) -> Response {
+		let path = request.path.split('?').next().unwrap_or_default();
+		let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+		match (request.method.as_str(), segments.as_slice()) {
+			("GET", ["days"]) => Response::ok(json!(days())),
+			("GET", ["status"]) => Response::ok(self.status()),
+			("POST", ["solve", year, day]) => match (year.parse(), day.parse()) {
+				(Ok(year), Ok(day)) => self.solve(year, day, request.body, deadline).await,
+				_ => Response::error(404, format!("no day {day} of {year}")),
+			},
+			(_, ["days" | "status"]) => Response::error(405, "use GET"),
+			(_, ["solve", _, _]) => Response::error(405, "use POST with the input as the body"),
+			_ => Response::error(404, format!("no such endpoint {path}")),
+		}
+	}
+
+	fn status(&self) -> Value {
+		json!({
+			"status": "ok",
+			"uptime_secs": self.started.elapsed().as_secs(),
+			"solving": self.solving.load(Ordering::Relaxed),
+			"solved": self.solved.load(Ordering::Relaxed),
+			"failed": self.failed.load(Ordering::Relaxed),
+			"timed_out": self.timed_out.load(Ordering::Relaxed),
+		})
+	}
+
+	async fn solve(
+		&self,
+		year: u16,
+		day: u8,
+		input: Vec<u8>,
+		deadline: tokio::time::Instant,
+	) -> Response {
+		if registered_day(year, day).is_none() {
+			return Response::error(404, format!("no day {day} of {year}"));
+		}
+		if std::str::from_utf8(&input).is_err() {
+			return Response::error(400, "the input must be UTF-8");
+		}
+		let Ok(Ok(job)) = timeout_at(deadline, self.jobs.clone().acquire_owned()).await else {
+			self.timed_out.fetch_add(1, Ordering::Relaxed);
+			return Response::error(504, "timed out waiting for a free job");
+		};
+		self.solving.fetch_add(1, Ordering::Relaxed);
+		// Timing out drops the solver, which kills it, so the job is free again for the next input
+		let res = timeout_at(deadline, self.run_solver(year, day, input)).await;
+		self.solving.fetch_sub(1, Ordering::Relaxed);
+		drop(job);
+		let body = match res {
+			Ok(Ok(body)) => body,
+			Ok(Err(err)) => {
+				self.failed.fetch_add(1, Ordering::Relaxed);
+				return Response::error(500, format!("{err:#}"));
+			}
+			Err(_) => {
+				self.timed_out.fetch_add(1, Ordering::Relaxed);
+				return Response::error(504, format!("not solved within {:?}", self.timeout));
+			}
+		};
+		let code = match body["status"].as_str() {
+			Some("ok") => 200,
+			Some("assumption" | "failed") => 422,
+			_ => 500,
+		};
+		let counter = if code == 200 {
+			&self.solved
+		} else {
+			&self.failed
+		};
+		counter.fetch_add(1, Ordering::Relaxed);
+		Response { status: code, body }
+	}
+
+	/// Solve `input` in a process of its own, giving the JSON it answers with.
+	async fn run_solver(&self, year: u16, day: u8, input: Vec<u8>) -> anyhow::Result<Value> {
+		let mut child = tokio::process::Command::new(&self.solver)
+			.args(["solve-input", &year.to_string(), &day.to_string()])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.kill_on_drop(true)
+			.spawn()
+			.context("failed to start the solver")?;
+		let mut stdin = child.stdin.take().context("the solver has no stdin")?;
+		// The solver reads all of its input before answering, so this can't block on its output
+		let written = stdin.write_all(&input).await;
+		drop(stdin);
+		let output = child
+			.wait_with_output()
+			.await
+			.context("the solver failed")?;
+		if !output.status.success() {
+			bail!("the solver exited with {}", output.status);
+		}
+		written.context("failed to send the input to the solver")?;
+		serde_json::from_slice(&output.stdout).context("the solver gave an invalid answer")
+	}
+}
+
+/// Solve one input read from stdin and print the answers as JSON, which is how `serve` runs each
+/// input.
+pub fn solve_input(year: u16, day: u8) -> anyhow::Result<()> {
+	let registered =
+		registered_day(year, day).with_context(|| format!("no day {day} of {year}"))?;
+	let mut input = String::new();
+	std::io::stdin()
+		.read_to_string(&mut input)
+		.context("failed to read the input")?;
+	let (status, taken) = run_input(&registered, DataFrom::owned(input), &quiet_app());
+	let mut body = json!({
+		"year": year,
+		"day": day,
+		"status": status.name(),
+		"nanos": taken.as_nanos(),
+	});
+	match status {
+		Status::Ok { score1, score2 } => {
+			body["part1"] = score1.into();
+			body["part2"] = score2.into();
+		}
+		Status::Assumption(error) | Status::Failed(error) | Status::Panicked(error) => {
+			body["error"] = error.into();
+		}
+	}
+	println!("{body}");
+	Ok(())
+}
+
+/// An app that has the days print and draw nothing, and write no files.  Its command is never run,
+/// the days only read its options.
+fn quiet_app() -> AocApp {
+	AocApp {
+		verbose: 0,
+		hide_scores: true,
+		trace: Vec::new(),
+		trace_file: None,
+		no_cache: true,
+		format: OutputFormat::Json,
+		inputs_dir: None,
+		artifacts_dir: PathBuf::new(),
+		artifacts: std::sync::Mutex::new(Vec::new()),
+		command: AocAppCommand::TUI,
+	}
+}
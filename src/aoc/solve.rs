@@ -0,0 +1,84 @@
+use crate::aoc::helpers::normalize;
+use crate::aoc::registry::{registered_day, registered_days};
+use anyhow::Context;
+use serde::Serialize;
+
+/// The answers of both parts of a day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Answers {
+	pub part1: String,
+	pub part2: String,
+}
+
+/// A day that can be solved, with the title of its puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DayInfo {
+	pub year: u16,
+	pub day: u8,
+	pub title: String,
+}
+
+/// Every day that can be solved, in order.
+#[must_use]
+pub fn days() -> Vec<DayInfo> {
+	registered_days()
+		.into_iter()
+		.map(|registered| DayInfo {
+			year: registered.year,
+			day: registered.day,
+			title: registered.title.to_string(),
+		})
+		.collect()
+}
+
+/// Solve both parts of `day` of `year` for `input`, with the day's options at their defaults, for
+/// embedding the solvers in other programs.  The input is normalized first as inputs read from
+/// files are.  Nothing is printed, and no files are written.
+pub fn solve(year: u16, day: u8, input: &str) -> anyhow::Result<Answers> {
+	let registered = registered_day(year, day)
+		.with_context(|| format!("no day {day} of {year}, or its year isn't enabled"))?;
+	let (part1, part2) = registered.solve(&normalize(input))?;
+	Ok(Answers { part1, part2 })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{days, solve, Answers};
+	use crate::aoc::year2023::Day1;
+
+	#[test]
+	fn solves_from_a_string() {
+		let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+		assert_eq!(
+			solve(2023, 1, input).unwrap(),
+			Answers {
+				part1: "142".to_string(),
+				part2: "142".to_string(),
+			}
+		);
+		assert_eq!(
+			solve(2023, 1, &input.replace('\n', "\r\n")).unwrap().part1,
+			"142"
+		);
+		assert_eq!(Day1::solve(input).unwrap(), (142, 142));
+		assert!(solve(2023, 26, input).is_err());
+		assert!(solve(2022, 1, "not a number\n").is_err());
+	}
+
+	#[test]
+	fn garbage_is_an_error() {
+		for (year, day, input) in [(2022, 2, "x\n"), (2022, 10, "x\n"), (2023, 7, "1,2 -> 3\n")] {
+			assert!(solve(year, day, input).is_err(), "{year} day {day}");
+		}
+	}
+
+	#[test]
+	fn days_have_titles() {
+		let all = days();
+		let volcanium = all.iter().find(|d| (d.year, d.day) == (2022, 16)).unwrap();
+		assert_eq!(volcanium.title, "Proboscidea Volcanium");
+		let trebuchet = all.iter().find(|d| (d.year, d.day) == (2023, 1)).unwrap();
+		assert_eq!(trebuchet.title, "Trebuchet?!");
+		assert!(all.iter().all(|d| !d.title.is_empty()));
+	}
+}
@@ -0,0 +1,266 @@
+use crate::{AocApp, OutputFormat};
+#[cfg(feature = "render")]
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+use std::io::{IsTerminal, Write};
+use std::num::NonZeroU8;
+use std::path::PathBuf;
+use std::time::Duration;
+use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
+
+/// A single cell of a visualization, drawn as `glyph` in a terminal or as a block
+/// of `color` in an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+	pub glyph: char,
+	pub color: [u8; 3],
+}
+
+impl Cell {
+	#[must_use]
+	pub const fn new(glyph: char, color: [u8; 3]) -> Self {
+		Self { glyph, color }
+	}
+}
+
+/// Something that can be drawn as a grid of cells, one frame at a time.
+pub trait Visualize {
+	/// The width and height in cells.
+	fn size(&self) -> (usize, usize);
+
+	/// The cell at `x`, `y`, both within `size`.
+	fn cell(&self, x: usize, y: usize) -> Cell;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RenderFormat {
+	/// An animated GIF
+	Gif,
+	/// A directory of numbered PNG frames
+	Png,
+	/// An animation in the terminal
+	Term,
+}
+
+/// The common options of days that can visualize their run.
+#[derive(Debug, Clone, Args)]
+pub struct RenderArgs {
+	/// Render a visualization of the run
+	#[clap(short, long, value_enum)]
+	pub render: Option<RenderFormat>,
+	/// Where to write the rendering, defaults to `<year>-<day>-render.gif` or a
+	/// `<year>-<day>-render` directory for PNG frames in the artifacts directory
+	#[clap(short, long, requires = "render")]
+	pub output: Option<PathBuf>,
+	/// Size in pixels of a cell in rendered images
+	#[clap(long, default_value_t = NonZeroU8::MIN)]
+	pub scale: NonZeroU8,
+	/// Delay between frames in milliseconds
+	#[clap(long, default_value_t = 50)]
+	pub delay: u16,
+}
+
+enum Sink {
+	Off,
+	#[cfg(feature = "render")]
+	Gif(Box<image::codecs::gif::GifEncoder<std::fs::File>>),
+	#[cfg(feature = "render")]
+	Png(PathBuf),
+	Term(BufferWriter),
+}
+
+/// Records frames of a [`Visualize`] to the output chosen by [`RenderArgs`], doing nothing
+/// when rendering was not asked for.
+pub struct Recorder {
+	sink: Sink,
+	#[cfg_attr(not(feature = "render"), allow(dead_code))]
+	scale: u32,
+	delay: u16,
+	frames: usize,
+}
+
+impl Recorder {
+	pub fn new(app: &AocApp, args: &RenderArgs, year: u16, day: u8) -> anyhow::Result<Self> {
+		let output = |ext: &str| match &args.output {
+			Some(path) => {
+				app.add_artifact(path.clone());
+				Ok(path.clone())
+			}
+			None => app.artifact_path(year, day, "render", ext),
+		};
+		let sink = match args.render {
+			None => Sink::Off,
+			// Kept apart from the JSON lines of the answers on stdout
+			Some(RenderFormat::Term) if app.format == OutputFormat::Json => {
+				Sink::Term(BufferWriter::stderr(if std::io::stderr().is_terminal() {
+					ColorChoice::Auto
+				} else {
+					ColorChoice::Never
+				}))
+			}
+			Some(RenderFormat::Term) => {
+				Sink::Term(BufferWriter::stdout(if std::io::stdout().is_terminal() {
+					ColorChoice::Auto
+				} else {
+					ColorChoice::Never
+				}))
+			}
+			#[cfg(feature = "render")]
+			Some(RenderFormat::Gif) => {
+				let path = output("gif")?;
+				let file = std::fs::File::create(&path)
+					.with_context(|| format!("failed to create {}", path.display()))?;
+				let mut gif = image::codecs::gif::GifEncoder::new_with_speed(file, 1);
+				gif.set_repeat(image::codecs::gif::Repeat::Infinite)?;
+				Sink::Gif(Box::new(gif))
+			}
+			#[cfg(feature = "render")]
+			Some(RenderFormat::Png) => {
+				let path = output("")?;
+				std::fs::create_dir_all(&path)
+					.with_context(|| format!("failed to create {}", path.display()))?;
+				Sink::Png(path)
+			}
+			#[cfg(not(feature = "render"))]
+			Some(format) => {
+				let _ = output;
+				anyhow::bail!("rendering to {format:?} needs the `render` feature")
+			}
+		};
+		Ok(Self {
+			sink,
+			scale: u32::from(args.scale.get()),
+			delay: args.delay,
+			frames: 0,
+		})
+	}
+
+	/// A recorder that records nothing, for solving a day without an app to render with.
+	#[must_use]
+	pub fn off() -> Self {
+		Self {
+			sink: Sink::Off,
+			scale: 1,
+			delay: 0,
+			frames: 0,
+		}
+	}
+
+	/// Whether frames are being recorded, so days can skip building them otherwise.
+	#[must_use]
+	pub fn is_recording(&self) -> bool {
+		!matches!(self.sink, Sink::Off)
+	}
+
+	/// The number of frames recorded so far.
+	#[must_use]
+	pub fn frames(&self) -> usize {
+		self.frames
+	}
+
+	/// Record a frame shown for the default delay.
+	pub fn frame(&mut self, frame: &impl Visualize) -> anyhow::Result<()> {
+		self.frame_for(frame, self.delay)
+	}
+
+	/// Record a frame shown for `delay` milliseconds.
+	pub fn frame_for(&mut self, frame: &impl Visualize, delay: u16) -> anyhow::Result<()> {
+		match &mut self.sink {
+			Sink::Off => return Ok(()),
+			#[cfg(feature = "render")]
+			Sink::Gif(gif) => gif.encode_frame(image::Frame::from_parts(
+				render_image(frame, self.scale),
+				0,
+				0,
+				image::Delay::from_numer_denom_ms(u32::from(delay), 1),
+			))?,
+			#[cfg(feature = "render")]
+			Sink::Png(dir) => {
+				let path = dir.join(format!("frame-{:05}.png", self.frames));
+				render_image(frame, self.scale)
+					.save(&path)
+					.with_context(|| format!("failed to write {}", path.display()))?;
+			}
+			Sink::Term(term) => {
+				let mut buf = term.buffer();
+				if buf.supports_color() {
+					// Clear the screen and move to the top left so the frames animate in place
+					write!(buf, "\x1b[2J\x1b[H")?;
+				}
+				write_term(&mut buf, frame)?;
+				term.print(&buf)?;
+				std::thread::sleep(Duration::from_millis(u64::from(delay)));
+			}
+		}
+		self.frames += 1;
+		Ok(())
+	}
+}
+
+fn write_term(buf: &mut termcolor::Buffer, frame: &impl Visualize) -> anyhow::Result<()> {
+	let (width, height) = frame.size();
+	let mut color = None;
+	for y in 0..height {
+		for x in 0..width {
+			let cell = frame.cell(x, y);
+			if color != Some(cell.color) {
+				let [r, g, b] = cell.color;
+				buf.set_color(ColorSpec::new().set_fg(Some(Color::Rgb(r, g, b))))?;
+				color = Some(cell.color);
+			}
+			write!(buf, "{}", cell.glyph)?;
+		}
+		writeln!(buf)?;
+	}
+	buf.reset()?;
+	Ok(())
+}
+
+/// Render `frame` as an image with each cell drawn as a `scale` sized square.
+#[cfg(feature = "render")]
+#[allow(clippy::cast_possible_truncation)]
+#[must_use]
+pub fn render_image(frame: &impl Visualize, scale: u32) -> image::RgbaImage {
+	let (width, height) = frame.size();
+	image::RgbaImage::from_fn(width as u32 * scale, height as u32 * scale, |x, y| {
+		let [r, g, b] = frame.cell((x / scale) as usize, (y / scale) as usize).color;
+		image::Rgba([r, g, b, 255])
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Cell, Visualize};
+
+	struct Checker;
+
+	impl Visualize for Checker {
+		fn size(&self) -> (usize, usize) {
+			(3, 2)
+		}
+
+		fn cell(&self, x: usize, y: usize) -> Cell {
+			if (x + y).is_multiple_of(2) {
+				Cell::new('#', [255, 255, 255])
+			} else {
+				Cell::new('.', [0, 0, 0])
+			}
+		}
+	}
+
+	#[test]
+	fn terminal() {
+		let mut buf = termcolor::Buffer::no_color();
+		super::write_term(&mut buf, &Checker).unwrap();
+		assert_eq!(buf.as_slice(), b"#.#\n.#.\n");
+	}
+
+	#[cfg(feature = "render")]
+	#[test]
+	fn image() {
+		let img = super::render_image(&Checker, 2);
+		assert_eq!(img.dimensions(), (6, 4));
+		assert_eq!(img.get_pixel(1, 1).0, [255, 255, 255, 255]);
+		assert_eq!(img.get_pixel(2, 1).0, [0, 0, 0, 255]);
+	}
+}
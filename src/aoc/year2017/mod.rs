@@ -1,18 +1,29 @@
+#[cfg(feature = "cli")]
 use crate::AocApp;
 use clap::Parser;
 
+/// The registered days of 2017, appended as a `[DayN, ...]` list to the arguments of the given
+/// macro, so everything generated for every day uses the same list.
+#[macro_export]
+macro_rules! year2017_days {
+	($($callback:ident)::+!($($args:tt)*)) => {
+		$($callback)::+! { $($args)* [] }
+	};
+}
+
 #[derive(Debug, Parser)]
 pub enum Year2017 {
 	/// Run all the Advent of Code 2017 days
 	RunAll,
 }
 
+#[cfg(feature = "cli")]
 impl Year2017 {
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		crate::run_days!(Year2017, self, app, [])
+		crate::year2017_days!(crate::run_days!(Year2017, self, app,))
 	}
 
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
-		crate::run_all_days!(Year2017, app, [])
+		crate::year2017_days!(crate::run_all_days!(Year2017, app,))
 	}
 }
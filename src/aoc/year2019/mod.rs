@@ -1,18 +1,29 @@
+#[cfg(feature = "cli")]
 use crate::AocApp;
 use clap::Parser;
 
+/// The registered days of 2019, appended as a `[DayN, ...]` list to the arguments of the given
+/// macro, so everything generated for every day uses the same list.
+#[macro_export]
+macro_rules! year2019_days {
+	($($callback:ident)::+!($($args:tt)*)) => {
+		$($callback)::+! { $($args)* [] }
+	};
+}
+
 #[derive(Debug, Parser)]
 pub enum Year2019 {
 	/// Run all the Advent of Code 2019 days
 	RunAll,
 }
 
+#[cfg(feature = "cli")]
 impl Year2019 {
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		crate::run_days!(Year2019, self, app, [])
+		crate::year2019_days!(crate::run_days!(Year2019, self, app,))
 	}
 
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
-		crate::run_all_days!(Year2019, app, [])
+		crate::year2019_days!(crate::run_all_days!(Year2019, app,))
 	}
 }
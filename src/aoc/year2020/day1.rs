@@ -11,11 +11,19 @@ pub struct Day1 {
 }
 
 impl Day1 {
+	pub const TITLE: &str = "Report Repair";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let mut score1 = 0;
 		let mut score2 = 0;
 		let mut nums =
-			map_trimmed_nonempty_lines_of_file(&self.input, |line| Ok(line.parse::<usize>()?))?;
+			map_trimmed_nonempty_lines_of_file(input, |line| Ok(line.parse::<usize>()?))?;
+		// Larger entries can't be part of a sum to 2020
+		nums.retain(|&n| n <= 2020);
 		nums.sort_unstable();
 
 		for a in 0..nums.len() {
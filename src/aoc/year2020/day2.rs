@@ -11,10 +11,16 @@ pub struct Day2 {
 }
 
 impl Day2 {
+	pub const TITLE: &str = "Password Philosophy";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let mut valid_count_1 = 0;
 		let mut valid_count_2 = 0;
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			match Self::is_valid(line)? {
 				(true, true) => {
 					valid_count_1 += 1;
@@ -55,8 +61,8 @@ impl Day2 {
 		if count < low {
 			valid_1 = false;
 		}
-		let low = low - 1;
-		let high = high - 1;
+		let low = low.checked_sub(1).context("positions start at 1")?;
+		let high = high.checked_sub(1).context("positions start at 1")?;
 		let mut chars = password.chars().enumerate();
 		for (i, cc) in chars.by_ref() {
 			if i == low {
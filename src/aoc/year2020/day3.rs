@@ -10,8 +10,14 @@ pub struct Day3 {
 }
 
 impl Day3 {
+	pub const TITLE: &str = "Toboggan Trajectory";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let map = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		let map = map_trimmed_nonempty_lines_of_file(input, |line| {
 			line.chars()
 				.map(|c| {
 					Ok(match c {
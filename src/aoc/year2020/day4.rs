@@ -86,34 +86,36 @@ impl Passport {
 }
 
 impl Day4 {
+	pub const TITLE: &str = "Passport Processing";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let mut current = Passport::default();
-		let mut passports = Vec::with_capacity(512);
-		process_trimmed_lines_of_file(&self.input, |line| {
-			if line.is_empty() {
-				passports.push(std::mem::take(&mut current));
-			} else {
-				for kv in line.split_whitespace().map(|kv| {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		let passports = Blocks::new(input)
+			.try_map(|block| {
+				let mut passport = Passport::default();
+				for kv in block.split_whitespace().map(|kv| {
 					kv.split_once(':')
 						.context("invalid key value pair, missing `:`")
 				}) {
 					let (key, value) = kv?;
 					match key {
-						"byr" => current.byr = value.into(),
-						"iyr" => current.iyr = value.into(),
-						"eyr" => current.eyr = value.into(),
-						"hgt" => current.hgt = value.into(),
-						"hcl" => current.hcl = value.into(),
-						"ecl" => current.ecl = value.into(),
-						"pid" => current.pid = value.into(),
-						"cid" => current.cid = value.into(),
+						"byr" => passport.byr = value.into(),
+						"iyr" => passport.iyr = value.into(),
+						"eyr" => passport.eyr = value.into(),
+						"hgt" => passport.hgt = value.into(),
+						"hcl" => passport.hcl = value.into(),
+						"ecl" => passport.ecl = value.into(),
+						"pid" => passport.pid = value.into(),
+						"cid" => passport.cid = value.into(),
 						_ => anyhow::bail!("invalid key: {}", key),
 					}
 				}
-			}
-			Ok(())
-		})?;
-		passports.push(current);
+				Ok(passport)
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?;
 
 		let score1 = passports.iter().filter(|p| p.is_valid()).count();
 
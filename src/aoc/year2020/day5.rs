@@ -44,8 +44,14 @@ impl FromStr for Seat {
 }
 
 impl Day5 {
+	pub const TITLE: &str = "Binary Boarding";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u16, u16)> {
-		let mut seats: Vec<Seat> = map_trimmed_nonempty_lines_of_file(&self.input, str::parse)?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u16, u16)> {
+		let mut seats: Vec<Seat> = map_trimmed_nonempty_lines_of_file(input, str::parse)?;
 		seats.sort_unstable();
 		let score1 = seats.last().context("no seats")?.0;
 		let score2 = seats
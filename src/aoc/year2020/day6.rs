@@ -1,5 +1,6 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
+use anyhow::bail;
 use clap::Parser;
 
 #[derive(Debug, Parser)]
@@ -10,24 +11,30 @@ pub struct Day6 {
 }
 
 impl Day6 {
+	pub const TITLE: &str = "Custom Customs";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
-		let mut current = (0u32, u32::MAX);
-		let mut groups = Vec::with_capacity(512);
-		process_trimmed_lines_of_file(&self.input, |line| {
-			if line.is_empty() {
-				groups.push(current);
-				current = (0, u32::MAX);
-			} else {
-				let mut person = 0;
-				for b in line.bytes() {
-					person |= 1 << (b - b'a');
-				}
-				current.0 |= person;
-				current.1 &= person;
-			}
-			Ok(())
-		})?;
-		groups.push(current);
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
+		let groups = Blocks::new(input)
+			.map(|block| {
+				block
+					.lines()
+					.map(str::trim)
+					.try_fold((0u32, u32::MAX), |group, line| {
+						let mut person = 0;
+						for b in line.bytes() {
+							if !b.is_ascii_lowercase() {
+								bail!("invalid answer {:?} in {line}", b as char);
+							}
+							person |= 1 << (b - b'a');
+						}
+						Ok((group.0 | person, group.1 & person))
+					})
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?;
 		let score1 = groups
 			.iter()
 			.copied()
@@ -1,6 +1,7 @@
+use crate::aoc::graph_export::{ExportGraph, GraphArgs};
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 use petgraph::prelude::*;
 use smol_str::SmolStr;
@@ -12,13 +13,36 @@ pub struct Day7 {
 	/// The input file to use with the parseable rules
 	#[clap(default_value_t = DataFrom::internal(2020, 7))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub graph: GraphArgs,
 }
 
 impl Day7 {
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+	pub const TITLE: &str = "Handy Haversacks";
+
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		let (scores, export) = self
+			.input
+			.solve(|input| Self::solve_exporting(input, self.graph.export_graph.is_some()))?;
+		if let Some(export) = export {
+			export.export(app, &self.graph, 2020, 7)?;
+		}
+		Ok(scores)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		Ok(Self::solve_exporting(input, false)?.0)
+	}
+
+	/// Solve both parts, and with `export` also build the graph of the rules to export.
+	#[allow(clippy::too_many_lines)]
+	fn solve_exporting(
+		input: &str,
+		export: bool,
+	) -> anyhow::Result<((usize, usize), Option<ExportGraph>)> {
 		let mut rules_graph = Graph::new();
 		let mut rules = HashMap::with_capacity(1024);
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			let (this_bag, can_contain) = line
 				.split_once(" bags contain ")
 				.context("invalid bag rule specifier")?;
@@ -44,15 +68,25 @@ impl Day7 {
 
 		for (this_node, can_contain) in rules.values() {
 			for (count, can_contain) in can_contain {
-				let other_node = &rules[can_contain].0;
+				let other_node = &rules
+					.get(can_contain)
+					.with_context(|| format!("no rule for {can_contain} bags"))?
+					.0;
 				rules_graph.add_edge(*this_node, *other_node, *count);
 			}
 		}
 
+		let shiny_gold = rules
+			.get("shiny gold")
+			.context("no rule for shiny gold bags")?
+			.0;
+		if petgraph::algo::is_cyclic_directed(&rules_graph) {
+			bail!("bags hold themselves");
+		}
+		let mut possible_external_colors = HashSet::with_capacity(rules.len());
 		let score1 = {
-			let mut possible_external_colors = HashSet::with_capacity(rules.len());
 			let mut to_process = Vec::with_capacity(rules.len());
-			to_process.push(rules["shiny gold"].0);
+			to_process.push(shiny_gold);
 			while let Some(next_node) = to_process.pop() {
 				for node in rules_graph.neighbors_directed(next_node, Incoming) {
 					if possible_external_colors.insert(node) {
@@ -63,19 +97,55 @@ impl Day7 {
 			possible_external_colors.len()
 		};
 
+		let mut total_bags = HashMap::with_capacity(rules.len());
 		let score2 = {
-			let mut total_bags = HashMap::with_capacity(rules.len());
 			let mut to_process = Vec::with_capacity(rules.len());
-			to_process.push((rules["shiny gold"].0, 1));
+			to_process.push((shiny_gold, 1));
 			while let Some((next_node, mult)) = to_process.pop() {
 				for edge in rules_graph.edges_directed(next_node, Outgoing) {
-					*total_bags.entry(edge.target()).or_default() += *edge.weight() * mult;
-					to_process.push((edge.target(), *edge.weight() * mult));
+					let count = edge
+						.weight()
+						.checked_mul(mult)
+						.context("too many bags to count")?;
+					let total: &mut usize = total_bags.entry(edge.target()).or_default();
+					*total = total.checked_add(count).context("too many bags to count")?;
+					to_process.push((edge.target(), count));
 				}
 			}
-			total_bags.values().sum::<usize>()
+			total_bags
+				.values()
+				.try_fold(0usize, |sum, &count| sum.checked_add(count))
+				.context("too many bags to count")?
 		};
 
-		Ok((score1, score2))
+		if !export {
+			return Ok(((score1, score2), None));
+		}
+		// Highlight the bags that can hold shiny gold, and the bags it holds, with the edges
+		// followed to find them
+		let mut export = ExportGraph::from_petgraph(
+			&rules_graph,
+			|node, bag| {
+				(
+					bag.to_string(),
+					total_bags.get(&node).unwrap_or(&0).to_string(),
+				)
+			},
+			ToString::to_string,
+		);
+		export.highlight_node(shiny_gold.index());
+		for edge in rules_graph.raw_edges() {
+			let (source, target) = (edge.source(), edge.target());
+			let holds = possible_external_colors.contains(&source)
+				&& (target == shiny_gold || possible_external_colors.contains(&target));
+			let held = total_bags.contains_key(&target)
+				&& (source == shiny_gold || total_bags.contains_key(&source));
+			if holds || held {
+				export.highlight_node(source.index());
+				export.highlight_node(target.index());
+				export.highlight_edges(source.index(), target.index());
+			}
+		}
+		Ok(((score1, score2), Some(export)))
 	}
 }
@@ -126,8 +126,14 @@ impl Program {
 }
 
 impl Day8 {
+	pub const TITLE: &str = "Handheld Halting";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i32, i32)> {
-		let insns = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(i32, i32)> {
+		let insns = map_trimmed_nonempty_lines_of_file(input, |line| {
 			line.parse::<Insns>().context("Failed to parse instruction")
 		})?;
 		let mut program = Program::new(insns);
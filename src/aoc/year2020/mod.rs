@@ -7,9 +7,28 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 
+pub use day1::Day1;
+pub use day2::Day2;
+pub use day3::Day3;
+pub use day4::Day4;
+pub use day5::Day5;
+pub use day6::Day6;
+pub use day7::Day7;
+pub use day8::Day8;
+
+#[cfg(feature = "cli")]
 use crate::AocApp;
 use clap::Parser;
 
+/// The registered days of 2020, appended as a `[DayN, ...]` list to the arguments of the given
+/// macro, so everything generated for every day uses the same list.
+#[macro_export]
+macro_rules! year2020_days {
+	($($callback:ident)::+!($($args:tt)*)) => {
+		$($callback)::+! { $($args)* [Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8] }
+	};
+}
+
 #[derive(Debug, Parser)]
 pub enum Year2020 {
 	/// Run all the Advent of Code 2020 days
@@ -40,21 +59,13 @@ pub enum Year2020 {
 	Day8(day8::Day8),
 }
 
+#[cfg(feature = "cli")]
 impl Year2020 {
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		crate::run_days!(
-			Year2020,
-			self,
-			app,
-			[Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8]
-		)
+		crate::year2020_days!(crate::run_days!(Year2020, self, app,))
 	}
 
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
-		crate::run_all_days!(
-			Year2020,
-			app,
-			[Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8]
-		)
+		crate::year2020_days!(crate::run_all_days!(Year2020, app,))
 	}
 }
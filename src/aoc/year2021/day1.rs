@@ -11,9 +11,14 @@ pub struct Day1 {
 }
 
 impl Day1 {
+	pub const TITLE: &str = "Sonar Sweep";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let nums =
-			map_trimmed_nonempty_lines_of_file(&self.input, |line| Ok(line.parse::<usize>()?))?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		let nums = map_trimmed_nonempty_lines_of_file(input, |line| Ok(line.parse::<usize>()?))?;
 		let score1 = nums
 			.iter()
 			.tuple_windows()
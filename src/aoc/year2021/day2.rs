@@ -20,20 +20,27 @@ enum Commands {
 
 #[derive(Default)]
 struct Pos {
-	depth: u32,
-	fore: u32,
-	aim: u32,
+	depth: i64,
+	fore: i64,
+	aim: i64,
 }
 
 impl Pos {
-	fn solution(&self) -> u32 {
-		self.depth * self.fore
+	fn solution(&self) -> anyhow::Result<u32> {
+		u32::try_from(self.depth * self.fore)
+			.with_context(|| format!("the submarine ended up at {}x{}", self.fore, self.depth))
 	}
 }
 
 impl Day2 {
+	pub const TITLE: &str = "Dive!";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
-		let commands = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
+		let commands = map_trimmed_nonempty_lines_of_file(input, |line| {
 			match line
 				.split_once(' ')
 				.context("input is not a command then space then a number")?
@@ -50,27 +57,27 @@ impl Day2 {
 			.iter()
 			.fold(Pos::default(), |mut pos, cmd| {
 				match cmd {
-					Commands::Forward(n) => pos.fore += n.get() as u32,
-					Commands::Down(n) => pos.depth += n.get() as u32,
-					Commands::Up(n) => pos.depth -= n.get() as u32,
+					Commands::Forward(n) => pos.fore += n.get() as i64,
+					Commands::Down(n) => pos.depth += n.get() as i64,
+					Commands::Up(n) => pos.depth -= n.get() as i64,
 				}
 				pos
 			})
-			.solution();
+			.solution()?;
 		let score2 = commands
 			.iter()
 			.fold(Pos::default(), |mut pos, cmd| {
 				match cmd {
-					Commands::Down(n) => pos.aim += n.get() as u32,
-					Commands::Up(n) => pos.aim -= n.get() as u32,
+					Commands::Down(n) => pos.aim += n.get() as i64,
+					Commands::Up(n) => pos.aim -= n.get() as i64,
 					Commands::Forward(n) => {
-						pos.fore += n.get() as u32;
-						pos.depth += pos.aim * n.get() as u32;
+						pos.fore += n.get() as i64;
+						pos.depth += pos.aim * n.get() as i64;
 					}
 				}
 				pos
 			})
-			.solution();
+			.solution()?;
 
 		Ok((score1, score2))
 	}
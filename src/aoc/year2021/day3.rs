@@ -1,6 +1,6 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 use itertools::Itertools;
 
@@ -14,12 +14,27 @@ pub struct Day3 {
 }
 
 impl Day3 {
+	pub const TITLE: &str = "Binary Diagnostic";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
 		let mut width = 0;
-		let mut nums = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
-			width = line.as_bytes().len();
+		let mut nums = map_trimmed_nonempty_lines_of_file(input, |line| {
+			if width != 0 && width != line.len() {
+				bail!("numbers are not all {width} bits wide");
+			}
+			width = line.len();
 			Ok(u32::from_str_radix(line, 2)?)
 		})?;
+		// Both answers multiply two numbers of the width, which have to fit
+		if !(1..=16).contains(&width) {
+			bail!(AssumptionFailed(format!(
+				"numbers are {width} bits wide rather than 1 to 16"
+			)));
+		}
 
 		let width: u32 = width.try_into()?;
 		let half_count = u32::try_from(nums.len())? / 2;
@@ -84,12 +84,18 @@ impl Card {
 }
 
 impl Day4 {
+	pub const TITLE: &str = "Giant Squid";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
 		let mut calls = Vec::with_capacity(1024);
 		let mut cards = Vec::with_capacity(128);
 		let mut card: Card = Card::default();
 		let mut card_line = 0;
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			if calls.is_empty() {
 				for num in line.split(',') {
 					calls.push(num.parse::<u8>().context("Failed to parse number")?);
@@ -101,6 +107,9 @@ impl Day4 {
 					.filter(|s| !s.is_empty())
 					.enumerate()
 				{
+					if i >= 5 {
+						bail!("card line has more than 5 numbers: {line}");
+					}
 					card.set(
 						card_line,
 						i,
@@ -1,6 +1,6 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 
 use std::fmt::{Debug, Formatter};
@@ -58,9 +58,22 @@ impl FromStr for Line {
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		let (point1, point2) = s.split_once(" -> ").context("invalid points line")?;
-		let point1 = point1.parse()?;
-		let point2 = point2.parse()?;
-		Ok(Line(point1, point2))
+		let point1: Point = point1.parse()?;
+		let point2: Point = point2.parse()?;
+		// The grid holds every point up to the furthest one
+		if [point1.x, point1.y, point2.x, point2.y]
+			.iter()
+			.any(|c| !(0..4096).contains(c))
+		{
+			bail!(AssumptionFailed(format!(
+				"points are expected within 0 to 4095: {s}"
+			)));
+		}
+		let line = Line(point1, point2);
+		if !line.is_straight() && (line.0.x - line.1.x).abs() != (line.0.y - line.1.y).abs() {
+			bail!("line is not horizontal, vertical or diagonal: {s}");
+		}
+		Ok(line)
 	}
 }
 
@@ -117,7 +130,7 @@ impl Grid {
 	#[allow(clippy::cast_sign_loss)]
 	fn place(&mut self, x: i32, y: i32) {
 		let index = y as usize * self.1 .0 + x as usize;
-		self.0[index] += 1;
+		self.0[index] = self.0[index].saturating_add(1);
 	}
 
 	fn count_above_1(&self) -> anyhow::Result<u32> {
@@ -128,8 +141,14 @@ impl Grid {
 }
 
 impl Day5 {
+	pub const TITLE: &str = "Hydrothermal Venture";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
-		let lines = map_trimmed_nonempty_lines_of_file(&self.input, Line::from_str)?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
+		let lines = map_trimmed_nonempty_lines_of_file(input, Line::from_str)?;
 		let size = lines.iter().fold((0, 0), |(x, y), line| {
 			let (min_x, min_y) = line.get_minimum_size();
 			(x.max(min_x), y.max(min_y))
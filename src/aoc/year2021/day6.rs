@@ -33,9 +33,15 @@ impl LifeStateCounts {
 }
 
 impl Day6 {
+	pub const TITLE: &str = "Lanternfish";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let mut counts = LifeStateCounts::default();
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			for num in line.split(',') {
 				let num: usize = num
 					.parse()
@@ -60,3 +66,29 @@ impl Day6 {
 		Ok((score1, score2))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::LifeStateCounts;
+	use proptest::prelude::*;
+
+	proptest! {
+		/// Against every fish simulated on its own.
+		#[test]
+		fn counts_match_each_fish(mut fish in prop::collection::vec(0..9u8, 1..10), days in 0..60) {
+			let mut counts = LifeStateCounts::default();
+			for &timer in &fish {
+				counts.0[usize::from(timer)] += 1;
+			}
+			for _ in 0..days {
+				counts.tick();
+				let born = bytecount::count(&fish, 0);
+				for timer in &mut fish {
+					*timer = if *timer == 0 { 6 } else { *timer - 1 };
+				}
+				fish.extend(std::iter::repeat_n(8, born));
+			}
+			prop_assert_eq!(counts.sum(), fish.len());
+		}
+	}
+}
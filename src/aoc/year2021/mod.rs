@@ -5,9 +5,26 @@ pub mod day4;
 pub mod day5;
 pub mod day6;
 
+pub use day1::Day1;
+pub use day2::Day2;
+pub use day3::Day3;
+pub use day4::Day4;
+pub use day5::Day5;
+pub use day6::Day6;
+
+#[cfg(feature = "cli")]
 use crate::AocApp;
 use clap::Parser;
 
+/// The registered days of 2021, appended as a `[DayN, ...]` list to the arguments of the given
+/// macro, so everything generated for every day uses the same list.
+#[macro_export]
+macro_rules! year2021_days {
+	($($callback:ident)::+!($($args:tt)*)) => {
+		$($callback)::+! { $($args)* [Day1, Day2, Day3, Day4, Day5, Day6] }
+	};
+}
+
 #[derive(Debug, Parser)]
 pub enum Year2021 {
 	/// Run all the Advent of Code 2021 days
@@ -32,12 +49,13 @@ pub enum Year2021 {
 	Day6(day6::Day6),
 }
 
+#[cfg(feature = "cli")]
 impl Year2021 {
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		crate::run_days!(Year2021, self, app, [Day1, Day2, Day3, Day4, Day5, Day6])
+		crate::year2021_days!(crate::run_days!(Year2021, self, app,))
 	}
 
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
-		crate::run_all_days!(Year2021, app, [Day1, Day2, Day3, Day4, Day5, Day6])
+		crate::year2021_days!(crate::run_all_days!(Year2021, app,))
 	}
 }
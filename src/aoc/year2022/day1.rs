@@ -10,20 +10,22 @@ pub struct Day1 {
 }
 
 impl Day1 {
+	pub const TITLE: &str = "Calorie Counting";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let mut highest = [0; 4];
-		process_trimmed_lines_of_file(&self.input, |line| {
-			if line.is_empty() {
-				highest.sort_unstable();
-				highest[0] = 0;
-				return Ok(());
-			}
-			highest[0] += line.parse::<usize>()?;
-			Ok(())
-		})?;
-		if highest[0] != 0 {
+		for calories in Blocks::new(input).try_map(|block| {
+			block
+				.lines()
+				.map(|line| Ok(line.trim().parse::<usize>()?))
+				.sum::<anyhow::Result<usize>>()
+		}) {
+			highest[0] = calories?;
 			highest.sort_unstable();
-			highest[0] = 0;
 		}
 		Ok((highest[3], highest[1..=3].iter().sum::<usize>()))
 	}
@@ -12,14 +12,23 @@ pub struct Day10 {
 }
 
 impl Day10 {
-	#[allow(clippy::cast_possible_wrap)]
+	pub const TITLE: &str = "Cathode-Ray Tube";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i64, String)> {
+		self.input.solve(Self::solve)
+	}
+
+	#[allow(clippy::cast_possible_wrap)]
+	pub fn solve(input: &str) -> anyhow::Result<(i64, String)> {
 		let mut xs = Vec::with_capacity(1024);
 		xs.push(1); // Buffer to align the cycles
 		xs.push(1);
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			let x = *xs.last().context("somehow xs is empty")?;
-			match line.split_at(4) {
+			match line
+				.split_at_checked(4)
+				.with_context(|| format!("unhandled opcode: {line:?}"))?
+			{
 				("noop", "") => xs.push(x),
 				("addx", amt) => {
 					xs.push(x);
@@ -36,7 +45,10 @@ impl Day10 {
 		// });
 
 		if xs.len() <= 220 {
-			bail!("input did not create enough states: {}", xs.len());
+			bail!(AssumptionFailed(format!(
+				"input did not create enough states: {}",
+				xs.len()
+			)));
 		}
 		let score1 = [20, 60, 100, 140, 180, 220]
 			.into_iter()
@@ -1,10 +1,12 @@
 use crate::aoc::helpers::*;
+use crate::aoc::progress::Progress;
 use crate::AocApp;
 use anyhow::{bail, Context};
 use clap::Parser;
 use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use tracing::{debug, trace};
 
 #[derive(Debug, Parser)]
 pub struct Day11 {
@@ -178,7 +180,7 @@ impl Monkey {
 		};
 		let worry = worry / DIV;
 		let worry = worry % test_mod;
-		let to = if (worry % self.test_div) == 0 {
+		let to = if worry.is_multiple_of(self.test_div) {
 			self.if_true_throw_to
 		} else {
 			self.if_false_throw_to
@@ -188,6 +190,8 @@ impl Monkey {
 }
 
 impl Day11 {
+	pub const TITLE: &str = "Monkey in the Middle";
+
 	fn round<const DIV: Worry>(monkeys: &mut [Monkey], test_mod: Worry) {
 		for i in 0..monkeys.len() {
 			while let Some((to, worry)) = monkeys[i].inspect_and_throw::<DIV>(test_mod) {
@@ -196,11 +200,19 @@ impl Day11 {
 		}
 	}
 
-	#[allow(clippy::too_many_lines)]
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(u64, u64)> {
+		let progress = Progress::new(app, "rounds", Some(10_000));
+		self.input
+			.solve(|input| Self::solve_reporting(input, &progress))
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
+		Self::solve_reporting(input, &Progress::hidden())
+	}
 
+	/// Solve both parts, reporting the rounds of the second part to `progress`.
+	#[allow(clippy::too_many_lines)]
+	fn solve_reporting(input: &str, progress: &Progress) -> anyhow::Result<(u64, u64)> {
 		let mut monkeys: Vec<Monkey> = input
 			.split("\n\n")
 			.map(Monkey::from_str)
@@ -213,38 +225,35 @@ impl Day11 {
 		let mut monkeys2 = monkeys.clone();
 
 		let test_mod = monkeys.iter().map(|m| m.test_div).product::<Worry>();
-		// dbg!(test_mod);
+		debug!(test_mod);
 
-		// monkeys.iter().for_each(|m| println!("{m}"));
-		// println!();
-		for _round in 1..=20 {
+		for m in &monkeys {
+			trace!("{m}");
+		}
+		for round in 1..=20 {
 			Self::round::<3>(&mut monkeys, test_mod);
-			// println!("After round {round}, the monkeys are holding items with these worry levels:");
-			// monkeys.iter().for_each(|m| println!("{m}"));
-			// println!();
+			trace!("After round {round}, the monkeys are holding items with these worry levels:");
+			for m in &monkeys {
+				trace!("{m}");
+			}
+		}
+		for m in &monkeys {
+			debug!("Monkey {} inspected items {} times.", m.id, m.inspections);
 		}
-		// for m in &monkeys {
-		// 	println!("Monkey {} inspected items {} times.", m.id, m.inspections);
-		// }
-		// println!();
 
 		monkeys.sort_by_key(|m| m.inspections);
 		let (_worst, best) = monkeys.split_at(monkeys.len() - 2);
 		let score1 = best.iter().map(|m| m.inspections).product::<u64>();
 
-		// for m in &monkeys2 {
-		// 	println!("Monkey {} inspected items {} times.", m.id, m.inspections);
-		// }
-		// println!();
-		for _round in 1..=10_000 {
+		for round in 1..=10_000 {
 			Self::round::<1>(&mut monkeys2, test_mod);
-			// if round == 1 || round == 20 || round % 1000 == 0 {
-			// 	println!("== After round {round} ==");
-			// 	for m in &monkeys2 {
-			// 		println!("Monkey {} inspected items {} times.", m.id, m.inspections);
-			// 	}
-			// 	println!();
-			// }
+			progress.set(round);
+			if round == 1 || round == 20 || round % 1000 == 0 {
+				debug!("== After round {round} ==");
+				for m in &monkeys2 {
+					debug!("Monkey {} inspected items {} times.", m.id, m.inspections);
+				}
+			}
 		}
 
 		monkeys2.sort_by_key(|m| m.inspections);
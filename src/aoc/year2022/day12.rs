@@ -1,17 +1,21 @@
 #![allow(clippy::similar_names)]
 
+use crate::aoc::graph_export::{ExportGraph, GraphArgs};
 use crate::aoc::helpers::*;
 use crate::AocApp;
 use anyhow::{bail, Context};
 use clap::Parser;
 use petgraph::algo::astar;
 use petgraph::graph::DiGraph;
+use tracing::{debug, trace};
 
 #[derive(Debug, Parser)]
 pub struct Day12 {
 	/// The input file of "heightmap"
 	#[clap(default_value_t = DataFrom::internal(2022, 12))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub graph: GraphArgs,
 }
 
 fn idx(x: usize, y: usize, width: usize) -> usize {
@@ -23,10 +27,28 @@ fn idx(x: usize, y: usize, width: usize) -> usize {
 // }
 
 impl Day12 {
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+	pub const TITLE: &str = "Hill Climbing Algorithm";
 
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(u64, u64)> {
+		let (scores, export) = self
+			.input
+			.solve(|input| Self::solve_exporting(input, self.graph.export_graph.is_some()))?;
+		if let Some(export) = export {
+			export.export(app, &self.graph, 2022, 12)?;
+		}
+		Ok(scores)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
+		Ok(Self::solve_exporting(input, false)?.0)
+	}
+
+	/// Solve both parts, and with `export` also build the graph of the heightmap to export.
+	#[allow(clippy::too_many_lines)]
+	fn solve_exporting(
+		input: &str,
+		export: bool,
+	) -> anyhow::Result<((u64, u64), Option<ExportGraph>)> {
 		let width = input.lines().next().context("input is empty")?.trim().len();
 		let height = input
 			.trim()
@@ -35,7 +57,7 @@ impl Day12 {
 			.copied()
 			.filter(|&c| c == b'\n')
 			.count() + 1;
-		// dbg!((width, height));
+		debug!("The heightmap is {width}x{height}");
 		let mut gmap: DiGraph<u8, bool> =
 			DiGraph::with_capacity(width * height, width * height * 4);
 		let mut start = None;
@@ -113,10 +135,10 @@ impl Day12 {
 			}
 		}
 
-		// dbg!(&gmap);
-		// dbg!(&mmap);
+		trace!(?gmap);
+		trace!(?mmap);
 
-		let (total_cost1, _path1) = astar(
+		let (total_cost1, path1) = astar(
 			&gmap,
 			start,
 			|finish| finish == end,
@@ -124,9 +146,10 @@ impl Day12 {
 			|n| ((b'z' - b'a') - gmap[n]) as u64,
 		)
 		.context("no path found")?;
-		// dbg!(total_cost1, _path1);
+		debug!("Climbed from the start in {total_cost1} steps");
+		trace!(?path1);
 
-		let (total_cost2, _path2) = astar(
+		let (total_cost2, path2) = astar(
 			&gmap,
 			end,
 			|finish| gmap[finish] == 0,
@@ -134,8 +157,24 @@ impl Day12 {
 			|n| gmap[n] as u64,
 		)
 		.context("no back path found")?;
-		// dbg!(total_cost2, _path2);
+		debug!("Climbed down to the lowest square in {total_cost2} steps");
+		trace!(?path2);
 
-		Ok((total_cost1, total_cost2))
+		if !export {
+			return Ok(((total_cost1, total_cost2), None));
+		}
+		// Edge weights are true in the direction that can be climbed, both paths are highlighted
+		let mut export = ExportGraph::from_petgraph(
+			&gmap,
+			|node, &height| {
+				let (x, y) = (node.index() % width, node.index() / width);
+				(format!("{x},{y}"), ((b'a' + height) as char).to_string())
+			},
+			ToString::to_string,
+		);
+		for path in [&path1, &path2] {
+			export.highlight_path(&path.iter().map(|n| n.index()).collect::<Vec<_>>());
+		}
+		Ok(((total_cost1, total_cost2), Some(export)))
 	}
 }
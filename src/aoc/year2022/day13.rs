@@ -11,6 +11,7 @@ use nom::sequence::*;
 use nom::IResult;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
+use tracing::trace;
 
 #[derive(Debug, Parser)]
 pub struct Day13 {
@@ -80,11 +81,7 @@ impl Debug for Data {
 
 impl Data {
 	fn parse_number(input: &str) -> IResult<&str, Value, VerboseError<&str>> {
-		let (input, value) = digit1(input)?;
-		let value = value
-			.parse()
-			.expect("should never fail as digit1 parsed only digits");
-		Ok((input, value))
+		map_res(digit1, str::parse)(input)
 	}
 
 	fn parse_list(input: &str) -> IResult<&str, Vec<Data>, VerboseError<&str>> {
@@ -113,25 +110,26 @@ impl Data {
 	fn parse_list_of_data_pairs(
 		input: &str,
 	) -> Result<Vec<(Data, Data)>, nom::Err<VerboseError<&str>>> {
-		let (input, list) = separated_list0(line_ending, Self::parse_data_pair)(input)?;
+		let (input, list) = separated_list0(line_ending, cut(Self::parse_data_pair))(input)?;
 		eof(input)?;
 		Ok(list)
 	}
 }
 
 impl Day13 {
+	pub const TITLE: &str = "Distress Signal";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+		self.input.solve(Self::solve)
+	}
 
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let data = Data::parse_list_of_data_pairs(input)
-			.map_err(|e| anyhow::anyhow!("parse error: {e:?}"))?;
+			.map_err(|e| ParseError::from_nom("<input>", input, e))?;
 
-		// for (a, b) in &data {
-		// 	println!("{a:?}");
-		// 	println!("{b:?}");
-		// 	println!("{:?}\n", a.cmp(b));
-		// }
+		for (a, b) in &data {
+			trace!("{a:?} is {:?} {b:?}", a.cmp(b));
+		}
 
 		let score1 = data
 			.iter()
@@ -151,7 +149,7 @@ impl Day13 {
 		let mut data: Vec<Data> = data.into_iter().flat_map(|(a, b)| [a, b]).collect();
 		data.extend(dividers.iter().cloned());
 		data.sort();
-		// dbg!(&data);
+		trace!(sorted = ?data);
 
 		let score2 = data
 			.iter()
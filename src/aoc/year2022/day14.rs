@@ -1,20 +1,20 @@
 use crate::aoc::helpers::*;
+use crate::aoc::visualize::{Cell, Recorder, RenderArgs, Visualize};
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 use std::fmt::{Display, Formatter};
-use std::num::NonZeroU8;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
+use tracing::{debug, trace};
 
 #[derive(Debug, Parser)]
 pub struct Day14 {
 	/// The input file of "lines"
 	#[clap(default_value_t = DataFrom::internal(2022, 14))]
 	pub input: DataFrom,
-	/// Render sand animation at scale
-	#[clap(short, long)]
-	pub render: Option<NonZeroU8>,
+	#[clap(flatten)]
+	pub render: RenderArgs,
 }
 
 type Coord = u16;
@@ -98,6 +98,13 @@ impl FromStr for Area {
 				Ok(path)
 			})
 			.collect::<anyhow::Result<_>>()?;
+		// The sand piles up at most as far to each side of 500 as the floor is deep, and all of it
+		// has to fit in the area drawn
+		if max_x >= 999 || max_y >= 496 {
+			bail!(AssumptionFailed(format!(
+				"rocks reach x {max_x} and y {max_y}, past the 1000 wide area"
+			)));
+		}
 		let height = max_y + 3;
 		let offset_x = min_x.min(500 - height);
 		let width = max_x.max(500 + height) - offset_x + 1;
@@ -145,7 +152,7 @@ impl Index<(Coord, Coord)> for Area {
 			// out of bounds is always air
 			return &self.oob;
 		}
-		&self.data[(x - self.offset_x + y * self.width) as usize]
+		&self.data[(x - self.offset_x) as usize + y as usize * self.width as usize]
 	}
 }
 
@@ -156,7 +163,7 @@ impl IndexMut<(Coord, Coord)> for Area {
 			self.oob = Tile::Air;
 			return &mut self.oob;
 		}
-		&mut self.data[(x - self.offset_x + y * self.width) as usize]
+		&mut self.data[(x - self.offset_x) as usize + y as usize * self.width as usize]
 	}
 }
 
@@ -170,7 +177,7 @@ enum MoveResult {
 impl Area {
 	#[allow(clippy::cast_possible_truncation)]
 	fn height(&self) -> Coord {
-		self.data.len() as Coord / self.width
+		(self.data.len() / self.width as usize) as Coord
 	}
 
 	/// Returns if it is filled or flows into the abyss
@@ -203,98 +210,64 @@ impl Area {
 			MoveResult::HitNormal
 		}
 	}
+}
 
-	fn render_image(&self, pixel_size: u32) -> image::RgbaImage {
-		let mut img = image::RgbaImage::new(self.width as u32, self.height() as u32);
-		for y in 0..self.height() {
-			for x in self.offset_x..(self.offset_x + self.width) {
-				let c = match self[(x, y)] {
-					Tile::Air => image::Rgba([0, 0, 0, 255]),
-					Tile::Stone => image::Rgba([189, 142, 62, 255]),
-					Tile::Sand => image::Rgba([252, 196, 108, 255]),
-				};
-				img.put_pixel((x - self.offset_x) as u32, y as u32, c);
-			}
-		}
-		if pixel_size > 1 {
-			img = image::imageops::resize(
-				&img,
-				pixel_size * self.width as u32,
-				pixel_size * self.height() as u32,
-				image::imageops::FilterType::Nearest,
-			);
+impl Visualize for Area {
+	fn size(&self) -> (usize, usize) {
+		(self.width as usize, self.height() as usize)
+	}
+
+	#[allow(clippy::cast_possible_truncation)]
+	fn cell(&self, x: usize, y: usize) -> Cell {
+		match self[(x as Coord + self.offset_x, y as Coord)] {
+			Tile::Air => Cell::new(' ', [0, 0, 0]),
+			Tile::Stone => Cell::new('█', [189, 142, 62]),
+			Tile::Sand => Cell::new('▒', [252, 196, 108]),
 		}
-		img
 	}
 }
 
 impl Day14 {
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+	pub const TITLE: &str = "Regolith Reservoir";
+
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		let mut recorder = Recorder::new(app, &self.render, 2022, 14)?;
+		self.input
+			.solve(|input| Self::solve_recording(input, &mut recorder))
+	}
 
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		Self::solve_recording(input, &mut Recorder::off())
+	}
+
+	/// Solve both parts, drawing the cave after every grain of sand to `recorder`.
+	fn solve_recording(input: &str, recorder: &mut Recorder) -> anyhow::Result<(usize, usize)> {
 		let mut area: Area = input.parse()?;
-		// dbg!((area.offset_x, area.width));
-		// println!("{area}");
+		debug!(area.offset_x, area.width);
 		let mut count = 0;
-		let mut count2;
-		if let Some(mult) = self.render {
-			let mult = mult.get();
-			let mut gif = image::codecs::gif::GifEncoder::new_with_speed(
-				std::fs::File::create("2022-14.gif")?,
-				1,
-			);
-			gif.set_repeat(image::codecs::gif::Repeat::Infinite)?;
-			loop {
-				let done = area.spawn_and_move_sand();
-				let delay = if done == MoveResult::HitNormal {
-					50
-				} else {
-					1000
-				};
-				gif.encode_frame(image::Frame::from_parts(
-					area.render_image(mult as u32),
-					0,
-					0,
-					image::Delay::from_numer_denom_ms(delay, 1),
-				))?;
-				if done != MoveResult::HitNormal {
-					break;
-				}
-				count += 1;
-			}
-			count2 = count + 1; // The +1 for the last dropped sand above that we didn't care about before, but we do now
-			while area.spawn_and_move_sand() != MoveResult::Full {
-				count2 += 1;
-				gif.encode_frame(image::Frame::from_parts(
-					area.render_image(mult as u32),
-					0,
-					0,
-					image::Delay::from_numer_denom_ms(50, 1),
-				))?;
-			}
-			count2 += 1; // For the last dropped sand
-			gif.encode_frame(image::Frame::from_parts(
-				area.render_image(mult as u32),
-				0,
-				0,
-				image::Delay::from_numer_denom_ms(1000, 1),
-			))?;
-		} else {
-			// println!("Initial State:\n{area}");
-			while area.spawn_and_move_sand() == MoveResult::HitNormal {
-				// if [1, 2, 5, 22, 24].contains(&count) {
-				// 	println!("Step: {count}\n{area}");
-				// }
-				count += 1;
+		trace!("Initial State:\n{area}");
+		loop {
+			let done = area.spawn_and_move_sand();
+			if done == MoveResult::HitNormal {
+				recorder.frame(&area)?;
+			} else {
+				recorder.frame_for(&area, 1000)?;
 			}
-			// println!("Step: {count}\n{area}");
-			count2 = count + 1; // The +1 for the last dropped sand above that we didn't care about before, but we do now
-			while area.spawn_and_move_sand() != MoveResult::Full {
-				count2 += 1;
+			if done != MoveResult::HitNormal {
+				break;
 			}
-			count2 += 1; // For the last dropped sand
+			count += 1;
+			trace!("Step: {count}\n{area}");
+		}
+		debug!("Step: {count}\n{area}");
+		let mut count2 = count + 1; // The +1 for the last dropped sand above that we didn't care about before, but we do now
+		while area.spawn_and_move_sand() != MoveResult::Full {
+			count2 += 1;
+			recorder.frame(&area)?;
 		}
+		count2 += 1; // For the last dropped sand
+		debug!("Step: {count2}\n{area}");
+		recorder.frame_for(&area, 1000)?;
 
 		Ok((count, count2))
 	}
@@ -1,10 +1,12 @@
 use crate::aoc::helpers::*;
+use crate::aoc::progress::Progress;
 use crate::AocApp;
 use anyhow::Context;
 use clap::Parser;
 use std::fmt::{Display, Formatter};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
+use tracing::trace;
 
 #[derive(Debug, Parser)]
 pub struct Day15 {
@@ -15,6 +17,7 @@ pub struct Day15 {
 
 type Coord = i64;
 
+#[derive(Debug)]
 struct Sensor {
 	loc: (Coord, Coord),
 	beacon: (Coord, Coord),
@@ -24,15 +27,19 @@ impl FromStr for Sensor {
 	type Err = anyhow::Error;
 
 	fn from_str(input: &str) -> Result<Self, Self::Err> {
-		let (_, input) = input.split_at(b"Sensor at x=".len());
+		let input = input
+			.strip_prefix("Sensor at x=")
+			.context("missing 'Sensor at x='")?;
 		let (sx, input) = input.split_once(", y=").context("missing sensor ', y='")?;
 		let (sy, input) = input
 			.split_once(": closest beacon is at x=")
 			.context("missing ': closest beacon is at x='")?;
 		let (bx, by) = input.split_once(", y=").context("missing beacon ', y='")?;
+		// Read as 32 bit so that distances and bounds can't overflow
+		let parse = |s: &str| -> anyhow::Result<Coord> { Ok(s.parse::<i32>()?.into()) };
 		Ok(Sensor {
-			loc: (sx.parse()?, sy.parse()?),
-			beacon: (bx.parse()?, by.parse()?),
+			loc: (parse(sx)?, parse(sy)?),
+			beacon: (parse(bx)?, parse(by)?),
 		})
 	}
 }
@@ -61,6 +68,7 @@ impl Sensor {
 	}
 }
 
+#[derive(Debug)]
 struct Sensors {
 	sensors: Vec<Sensor>,
 	bounds: (RangeInclusive<Coord>, RangeInclusive<Coord>),
@@ -221,6 +229,7 @@ impl Sensors {
 		&self,
 		xr: RangeInclusive<Coord>,
 		yr: RangeInclusive<Coord>,
+		progress: &Progress,
 	) -> Option<(Coord, Coord)> {
 		fn get_range_on_y(s: &Sensor, y: Coord) -> RangeInclusive<Coord> {
 			let range = s.range();
@@ -258,7 +267,13 @@ impl Sensors {
 			x
 		}
 		let mut line_cache: Vec<(RangeInclusive<Coord>, &Sensor)> = Vec::new();
+		let first = *yr.start();
 		for y in yr {
+			// Only every so often, as each row is quick
+			let done = y.abs_diff(first);
+			if done % 1024 == 0 {
+				progress.set(done);
+			}
 			rebuild_ranges_on_y(&mut line_cache, y);
 			let x = find_empty_in_cache(&line_cache, *xr.start());
 			if x <= *xr.end() {
@@ -288,12 +303,22 @@ impl Data {
 }
 
 impl Day15 {
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i32, i64)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+	pub const TITLE: &str = "Beacon Exclusion Zone";
+
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(i32, i64)> {
+		let progress = Progress::new(app, "rows scanned", Some(4_000_001));
+		self.input
+			.solve(|input| Self::solve_reporting(input, &progress))
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(i32, i64)> {
+		Self::solve_reporting(input, &Progress::hidden())
+	}
 
+	/// Solve both parts, reporting the rows scanned for the second part to `progress`.
+	fn solve_reporting(input: &str, progress: &Progress) -> anyhow::Result<(i32, i64)> {
 		let sensors: Sensors = input.parse()?;
-		// println!("{sensors}");
+		trace!(sensors = ?sensors.sensors, bounds = ?sensors.bounds);
 
 		let score1 = sensors.fold_cells::<_, anyhow::Error>(
 			0,
@@ -303,10 +328,63 @@ impl Day15 {
 		)?;
 
 		let coords2 = sensors
-			.find_empty(0..=4_000_000, 0..=4_000_000)
+			.find_empty(0..=4_000_000, 0..=4_000_000, progress)
 			.context("didn't find an unknown spot")?;
 		let score2 = coords2.0 * 4_000_000 + coords2.1;
 
 		Ok((score1, score2))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{Coord, Sensors};
+	use crate::aoc::progress::Progress;
+	use proptest::prelude::*;
+	use std::fmt::Write;
+	use std::ops::RangeInclusive;
+
+	/// Check every cell, along each row in turn.
+	fn find_empty_brute_force(
+		sensors: &Sensors,
+		xr: RangeInclusive<Coord>,
+		yr: RangeInclusive<Coord>,
+	) -> Option<(Coord, Coord)> {
+		yr.flat_map(|y| xr.clone().map(move |x| (x, y)))
+			.find(|&(x, y)| !sensors.sensors.iter().any(|s| s.in_known_range(x, y)))
+	}
+
+	fn sensors() -> impl Strategy<Value = Sensors> {
+		prop::collection::vec(
+			(
+				0..20 as Coord,
+				0..20 as Coord,
+				-4..=4 as Coord,
+				-4..=4 as Coord,
+			),
+			1..8,
+		)
+		.prop_map(|sensors| {
+			let mut input = String::new();
+			for (x, y, dx, dy) in sensors {
+				let (bx, by) = (x + dx, y + dy);
+				writeln!(
+					input,
+					"Sensor at x={x}, y={y}: closest beacon is at x={bx}, y={by}"
+				)
+				.unwrap();
+			}
+			input.parse().unwrap()
+		})
+	}
+
+	proptest! {
+		#[test]
+		fn find_empty_matches_brute_force(sensors in sensors()) {
+			prop_assert_eq!(
+				sensors.find_empty(0..=20, 0..=20, &Progress::hidden()),
+				find_empty_brute_force(&sensors, 0..=20, 0..=20)
+			);
+		}
+	}
+}
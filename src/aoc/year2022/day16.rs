@@ -1,21 +1,42 @@
-/// Something here is not right in part 2, even though it solves my input...
+use crate::aoc::graph_export::{ExportGraph, GraphArgs};
 use crate::aoc::helpers::*;
+use crate::aoc::progress::Progress;
 use crate::AocApp;
 use ahash::{HashMap, HashMapExt};
-use anyhow::{bail, Context};
-use clap::Parser;
+use anyhow::bail;
+use clap::{Parser, ValueEnum};
 use nalgebra::DMatrix;
 use petgraph::dot::Dot;
 use petgraph::graph::{DiGraph, NodeIndex};
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
+use tracing::{debug, trace};
 
 #[derive(Debug, Parser)]
 pub struct Day16 {
 	/// The input file of "valve information"
 	#[clap(default_value_t = DataFrom::internal(2022, 16))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub graph: GraphArgs,
+	/// How to search the orders of opening the valves
+	#[clap(long = "impl", value_enum, default_value_t)]
+	pub implementation: Implementation,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Implementation {
+	/// A search of both openers at once, taking turns by who is free first
+	Search,
+	/// Every order of opening the valves that fits in the time, keeping the best of each set of
+	/// opened valves, with the two openers taking the best pair of sets that don't overlap
+	#[default]
+	Permutations,
+}
+
+fn first_word(line: &str) -> &str {
+	line.split(' ').next().unwrap_or(line)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -34,25 +55,54 @@ struct Valve {
 }
 
 impl Day16 {
+	pub const TITLE: &str = "Proboscidea Volcanium";
+
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+		self.input
+			.solve(|input| Self::solve_with(input, self.implementation, Some((app, &self.graph))))
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		Self::solve_with(input, Implementation::default(), None)
+	}
+
+	/// Solve both parts with `implementation`, and given an app also write the graphs it asks for
+	/// and show how far the searches have got.
+	#[allow(clippy::too_many_lines)]
+	fn solve_with(
+		input: &str,
+		implementation: Implementation,
+		app: Option<(&AocApp, &GraphArgs)>,
+	) -> anyhow::Result<(usize, usize)> {
+		let error =
+			|found: &str, expected: &str| ParseError::new("<input>", input, found, expected);
+		let new_progress = |what, total| {
+			app.map_or_else(Progress::hidden, |(app, _)| Progress::new(app, what, total))
+		};
 
 		let mut nodes = HashMap::new();
 		let mut wanted_nodes = Vec::new();
 		let mut graph = DiGraph::new();
 
 		for line in input.lines() {
-			let ("Valve ", line) = line.split_at(6) else {
-				bail!("invalid valve line: {}", line);
-			};
-			let (id, line) = line.split_at(2);
-			let id = ID(id.as_bytes().try_into().context("invalid ID format")?);
-			let (" has flow rate=", line) = line.split_at(15) else {
-				bail!("invalid flow rate line: {}", line);
-			};
-			let (flow_rate, line) = line.split_once(';').context("invalid flow rate format")?;
-			let flow_rate = flow_rate.parse().context("flow rate is not a number")?;
+			let line = line
+				.strip_prefix("Valve ")
+				.ok_or_else(|| error(first_word(line), "`Valve `"))?;
+			let id = line
+				.get(..2)
+				.and_then(|id| Some(ID(id.as_bytes().try_into().ok()?)))
+				.ok_or_else(|| error(first_word(line), "a two letter valve ID"))?;
+			let line = &line[2..];
+			let line = line.strip_prefix(" has flow rate=").ok_or_else(|| {
+				let found = line.get(..15).unwrap_or(line);
+				error(found, "` has flow rate=`")
+			})?;
+			let (flow_rate, line) = line
+				.split_once(';')
+				.ok_or_else(|| error(line, "`;` after the flow rate"))?;
+			let flow_rate = flow_rate
+				.parse()
+				.map_err(|_| error(flow_rate, "a flow rate number"))?;
 			let mut valve = Valve {
 				id,
 				flow_rate,
@@ -63,17 +113,17 @@ impl Day16 {
 				.enumerate()
 				.filter(|(_, b)| *b == b' ')
 				.nth(4)
-				.context("invalid valve line")?
+				.ok_or_else(|| error(line, "`tunnels lead to valves`"))?
 				.0;
 			let (_, line) = line.split_at(at + 1);
 			for (i, node) in line.split(", ").enumerate() {
 				if i >= 5 {
-					bail!("too many tunnels: {}", line);
+					return Err(error(node, "at most 5 tunnels").into());
 				}
 				let id = ID(node
 					.as_bytes()
 					.try_into()
-					.context("invalid edge ID format")?);
+					.map_err(|_| error(node, "a two letter valve ID"))?);
 				valve.nexts[i] = Some(id);
 			}
 			let node = graph.add_node((valve.id, valve.flow_rate));
@@ -108,134 +158,140 @@ impl Day16 {
 			}
 		}
 
-		if app.verbose > 1 {
-			std::fs::write(
-				"2022-16.dot",
-				format!("{:?}", Dot::with_config(&graph, &[])),
-			)?;
+		if let Some((app, graph_args)) = app {
+			if app.verbose > 1 {
+				std::fs::write(
+					app.artifact_path(2022, 16, "debug-graph", "dot")?,
+					format!("{:?}", Dot::with_config(&graph, &[])),
+				)?;
+			}
+			if graph_args.export_graph.is_some() {
+				// Highlight the valves worth opening, and the start, every tunnel takes a minute
+				let mut export = ExportGraph::from_petgraph(
+					&graph,
+					|_, (id, flow_rate)| (id.to_string(), flow_rate.to_string()),
+					|()| "1".to_string(),
+				);
+				for (node, _) in &wanted_nodes {
+					export.highlight_node(node.index());
+				}
+				export.export(app, graph_args, 2022, 16)?;
+			}
 		}
-		// dbg!(&graph);
-		// for xs in paths.row_iter() {
-		// 	for x in &xs {
-		// 		print!("{:3} ", x);
-		// 	}
-		// 	println!();
-		// }
+		trace!(?graph);
+		debug!("Travel times between the valves worth opening:{paths}");
 
-		let mut remaining: VecDeque<_> = (1..wanted_nodes.len()).collect();
-		let score1 = find_best_score_in_time(&paths, &wanted_nodes, 0, 0, 0, &mut remaining);
-		let mut remaining: VecDeque<_> = (1..wanted_nodes.len()).collect();
-		let score2 = find_best_score_in_time_dual(
-			&paths,
-			&wanted_nodes,
-			0,
-			0,
-			(0, 0),
-			CurState::Neither,
-			&mut remaining,
-		)?;
-		// for i in 0..wanted_nodes.len() {
-		// 	print!("{i:<2} ");
-		// }
-		// println!();
-		// for node in &wanted_nodes {
-		// 	let id = graph[node.0].0;
-		// 	print!("{id} ");
-		// }
-		// println!();
-
-		// let counts: Vec<usize> = (1..wanted_nodes.len()).collect();
-		// let mut best_score = 0;
-		// let mut best_path = Vec::with_capacity(wanted_nodes.len());
-		// let mut cache_path = Vec::with_capacity(wanted_nodes.len());
-		// for perm in permute::permutations_of(&counts) {
-		// 	// {
-		// 	// 	let get = |id: &[u8; 2]| {
-		// 	// 		let node = nodes[id].1;
-		// 	// 		wanted_nodes.iter().position(|&n| n == node).unwrap()
-		// 	// 	};
-		// 	// 	let perm = [
-		// 	// 		// get(b"AA"),
-		// 	// 		get(b"DD"),
-		// 	// 		get(b"BB"),
-		// 	// 		get(b"JJ"),
-		// 	// 		get(b"HH"),
-		// 	// 		get(b"EE"),
-		// 	// 		get(b"CC"),
-		// 	// 	];
-		// 	// 	let perm = perm.iter();
-		// 	let perm = perm.collect::<Vec<_>>();
-		// 	// for p in &perm {
-		// 	// 	let id = graph[wanted_nodes[**p]].0;
-		// 	// 	print!("{}{} ", id[0], id[1]);
-		// 	// }
-		// 	// print!("-> ");
-		// 	let perm = perm.iter().copied();
-		// 	cache_path.clear();
-		// 	let mut prior = 0;
-		// 	let mut rate = 0u64;
-		// 	let mut score = 0u64;
-		// 	let mut time = 1;
-		// 	for i in perm.copied() {
-		// 		let t = paths[(prior, i)] as u64 + 1; // +1 for activating `i` valve
-		// 							  // println!("Minute {time}");
-		// 							  // for tt in 1..=t {
-		// 							  // 	println!(
-		// 							  // 		"Valves {:?} are open, releasing {rate} pressure, {} released so far",
-		// 							  // 		cache_path
-		// 							  // 			.iter()
-		// 							  // 			.map(|&i| wanted_nodes[i])
-		// 							  // 			.map(|i| graph[i])
-		// 							  // 			.map(|i: ([char; 2], _)| format!("{}{}", i.0[0], i.0[1]))
-		// 							  // 			.collect::<Vec<_>>(),
-		// 							  // 		score + (rate * tt)
-		// 							  // 	);
-		// 							  // }
-		// 							  // println!(
-		// 							  // 	"Moved to {}{} and opening valve to change the rate to {}\n",
-		// 							  // 	graph[wanted_nodes[i]].0[0],
-		// 							  // 	graph[wanted_nodes[i]].0[1],
-		// 							  // 	rate + graph[wanted_nodes[i]].1 as u64
-		// 							  // );
-		// 		if time + t > 30 {
-		// 			score += rate * (31 - time);
-		// 			time = 30;
-		// 			break;
-		// 		}
-		// 		score += rate * t;
-		// 		rate += graph[wanted_nodes[i]].1 as u64;
-		// 		time += t;
-		// 		cache_path.push(i);
-		// 		prior = i;
-		// 		// if cache_path[0] == 3 && (cache_path.len() == 1 || cache_path[1] == 1) {
-		// 		print!("{i}:{t}:{time}:{rate}:{score}  ");
-		// 		// }
-		// 	}
-		// 	if time < 30 {
-		// 		score += rate * (31 - time);
-		// 		time = 30;
-		// 	}
-		// 	println!("-> {time} -> {rate} -> {score}");
-		// 	if score > best_score {
-		// 		best_score = score;
-		// 		best_path.clear();
-		// 		best_path.extend(cache_path.iter().copied());
-		// 	}
-		// }
-		// dbg!(best_score);
-		// let mut prior = 0;
-		// print!("AA");
-		// for i in best_path.iter().copied() {
-		// 	let id = &graph[wanted_nodes[i]].0;
-		// 	print!(" {}> {}{}", paths[(prior, i)] + 1, id[0], id[1]);
-		// 	prior = i;
-		// }
-		// println!();
+		let (score1, score2) = match implementation {
+			Implementation::Search => {
+				let mut remaining: VecDeque<_> = (1..wanted_nodes.len()).collect();
+				let score1 =
+					find_best_score_in_time(&paths, &wanted_nodes, 0, 0, 0, &mut remaining);
+				trace!("Best release opening valves alone: {score1}");
+				let mut remaining: VecDeque<_> = (1..wanted_nodes.len()).collect();
+				let score2 = find_best_score_in_time_dual(
+					&paths,
+					&wanted_nodes,
+					0,
+					0,
+					(0, 0),
+					CurState::Neither,
+					&mut remaining,
+					&new_progress("search nodes expanded", None),
+				)?;
+				(score1, score2)
+			}
+			Implementation::Permutations => {
+				let progress = new_progress("orders of opening tried", None);
+				let alone = best_per_opened_set(&paths, &wanted_nodes, 30, &progress)?;
+				let score1 = alone.iter().copied().max().unwrap_or(0);
+				let mut best_within =
+					best_per_opened_set(&paths, &wanted_nodes, MAX_DUAL_TIME, &progress)?;
+				drop(progress);
+				let all = best_within.len() - 1;
+				let progress = new_progress(
+					"sets widened",
+					Some(u64::from(all.count_ones()) * (all as u64 + 1)),
+				);
+				// Widen each set to the best of any of its subsets, so the other opener can take
+				// every valve left over rather than exactly those
+				for bit in (0..all.count_ones()).map(|bit| 1 << bit) {
+					for set in 0..=all {
+						if set & bit != 0 {
+							best_within[set] = best_within[set].max(best_within[set ^ bit]);
+						}
+					}
+					progress.inc(all as u64 + 1);
+				}
+				drop(progress);
+				let score2 = (0..=all)
+					.map(|set| best_within[set] + best_within[all ^ set])
+					.max()
+					.unwrap_or(0);
+				(score1, score2)
+			}
+		};
+		for (i, node) in wanted_nodes.iter().enumerate() {
+			debug!("Valve {i} is {}", graph[node.0].0);
+		}
 
 		Ok((score1, score2))
 	}
 }
 
+/// The most pressure released in `time` minutes by opening each set of the valves, as a bitmask
+/// of the valves besides the start, over every order of opening them that fits in the time.
+fn best_per_opened_set(
+	travel_times: &DMatrix<u8>,
+	rates: &[(NodeIndex, usize)],
+	time: u8,
+	progress: &Progress,
+) -> anyhow::Result<Vec<usize>> {
+	#[allow(clippy::too_many_arguments)]
+	fn visit(
+		travel_times: &DMatrix<u8>,
+		rates: &[(NodeIndex, usize)],
+		at: usize,
+		time_left: u8,
+		opened: usize,
+		released: usize,
+		best: &mut [usize],
+		progress: &Progress,
+	) {
+		progress.inc(1);
+		best[opened] = best[opened].max(released);
+		for next in 1..rates.len() {
+			let bit = 1 << (next - 1);
+			// + 1 for activating the valve
+			let taken = travel_times[(at, next)] + 1;
+			if opened & bit != 0 || taken >= time_left {
+				continue;
+			}
+			let time_left = time_left - taken;
+			let released = released + time_left as usize * rates[next].1;
+			visit(
+				travel_times,
+				rates,
+				next,
+				time_left,
+				opened | bit,
+				released,
+				best,
+				progress,
+			);
+		}
+	}
+
+	let valves = rates.len().saturating_sub(1);
+	if valves > 20 {
+		bail!("too many valves worth opening to try every set of them: {valves}");
+	}
+	let mut best = vec![0; 1 << valves];
+	if !rates.is_empty() {
+		visit(travel_times, rates, 0, time, 0, 0, &mut best, progress);
+	}
+	Ok(best)
+}
+
 fn find_best_score_in_time(
 	travel_times: &DMatrix<u8>,
 	rates: &Vec<(NodeIndex, usize)>,
@@ -244,10 +300,7 @@ fn find_best_score_in_time(
 	prior: usize,
 	remaining: &mut VecDeque<usize>,
 ) -> usize {
-	// println!(
-	// 	"{}{cur} {time} {rate} {remaining:?}",
-	// 	" ".repeat(20 - remaining.len()),
-	// );
+	trace!(time, valve = prior, rate, ?remaining, "search step");
 	// Default best score is if we don't move, spend remaining time at current node
 	let mut best_score = (30 - time as usize) * rate;
 	for _ in 0..remaining.len() {
@@ -269,7 +322,6 @@ fn find_best_score_in_time(
 		best_score = best_score.max(score);
 		remaining.push_back(cur);
 	}
-	// println!("{}->{best_score}", " ".repeat(20 - remaining.len()),);
 	best_score
 }
 
@@ -295,7 +347,21 @@ impl Display for CurState {
 
 const MAX_DUAL_TIME: u8 = 26;
 
-#[allow(clippy::needless_pass_by_value, clippy::too_many_lines)]
+/// The pressure released from `time` on when the only valve still opened is `cur`, reached in `t`.
+fn release_alone(rates: &[(NodeIndex, usize)], time: u8, rate: usize, t: u8, cur: usize) -> usize {
+	let left = (MAX_DUAL_TIME - time) as usize;
+	if time + t > MAX_DUAL_TIME {
+		rate * left
+	} else {
+		rate * left + rates[cur].1 * (MAX_DUAL_TIME - time - t) as usize
+	}
+}
+
+#[allow(
+	clippy::needless_pass_by_value,
+	clippy::too_many_arguments,
+	clippy::too_many_lines
+)]
 fn find_best_score_in_time_dual(
 	travel_times: &DMatrix<u8>,
 	rates: &Vec<(NodeIndex, usize)>,
@@ -304,7 +370,10 @@ fn find_best_score_in_time_dual(
 	prior: (usize, usize),
 	cur: CurState,
 	remaining: &mut VecDeque<usize>,
+	progress: &Progress,
 ) -> anyhow::Result<usize> {
+	progress.inc(1);
+	trace!(time, valves = ?prior, rate, %cur, ?remaining, "search step");
 	if time > MAX_DUAL_TIME {
 		bail!("too much time spent {time} > {MAX_DUAL_TIME}");
 	}
@@ -323,6 +392,7 @@ fn find_best_score_in_time_dual(
 					(prior.0, 0),
 					CurState::Both((t0, cur0), (30, 0)),
 					remaining,
+					progress,
 				)?;
 				best_score = best_score.max(score);
 				for _ in 0..(remaining.len()) {
@@ -336,6 +406,7 @@ fn find_best_score_in_time_dual(
 						(prior.0, prior.1),
 						CurState::Both((t0, cur0), (t1, cur1)),
 						remaining,
+						progress,
 					)?;
 					best_score = best_score.max(score);
 					remaining.push_back(cur1);
@@ -354,12 +425,15 @@ fn find_best_score_in_time_dual(
 					(0, prior.1),
 					CurState::Both((30, 0), (t1, cur1)),
 					remaining,
+					progress,
 				)?;
 				best_score = best_score.max(score);
 				remaining.push_back(cur1);
 			}
 		}
 		CurState::Left(t0, cur0) => {
+			// Right may open nothing more, which still leaves left opening the valve it is going to
+			best_score = best_score.max(release_alone(rates, time, rate, t0, cur0));
 			for _ in 0..remaining.len() {
 				let cur1 = remaining.pop_front().unwrap();
 				let t1 = travel_times[(prior.1, cur1)] + 1; // + 1 for activating the valve
@@ -371,12 +445,14 @@ fn find_best_score_in_time_dual(
 					(prior.0, prior.1),
 					CurState::Both((t0, cur0), (t1, cur1)),
 					remaining,
+					progress,
 				)?;
 				best_score = best_score.max(score);
 				remaining.push_back(cur1);
 			}
 		}
 		CurState::Right(t1, cur1) => {
+			best_score = best_score.max(release_alone(rates, time, rate, t1, cur1));
 			for _ in 0..remaining.len() {
 				let cur0 = remaining.pop_front().unwrap();
 				let t0 = travel_times[(prior.0, cur0)] + 1; // + 1 for activating the valve
@@ -388,6 +464,7 @@ fn find_best_score_in_time_dual(
 					(prior.0, prior.1),
 					CurState::Both((t0, cur0), (t1, cur1)),
 					remaining,
+					progress,
 				)?;
 				best_score = best_score.max(score);
 				remaining.push_back(cur0);
@@ -407,6 +484,7 @@ fn find_best_score_in_time_dual(
 							(cur0, prior.1),
 							CurState::Right(t1 - t0, cur1),
 							remaining,
+							progress,
 						)?
 				};
 				best_score = best_score.max(score);
@@ -424,6 +502,7 @@ fn find_best_score_in_time_dual(
 							(cur0, cur1),
 							CurState::Neither,
 							remaining,
+							progress,
 						)?
 				};
 				best_score = best_score.max(score);
@@ -441,6 +520,7 @@ fn find_best_score_in_time_dual(
 							(prior.0, cur1),
 							CurState::Left(t0 - t1, cur0),
 							remaining,
+							progress,
 						)?
 				};
 				best_score = best_score.max(score);
@@ -449,3 +529,23 @@ fn find_best_score_in_time_dual(
 	}
 	Ok(best_score)
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::aoc::helpers::{DataFrom, EMPTY_TUI_AOC_APP};
+	use crate::aoc::registry::registered_day;
+
+	crate::run_basic_tests!(super::Day16, examples: (2022, 16));
+
+	#[test]
+	fn search_examples() {
+		let run = registered_day(2022, 16)
+			.unwrap()
+			.build_impl(DataFrom::example(2022, 16, 1), "search")
+			.unwrap();
+		assert_eq!(
+			run(&EMPTY_TUI_AOC_APP).unwrap(),
+			("1651".to_string(), "1707".to_string())
+		);
+	}
+}
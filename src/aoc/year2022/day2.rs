@@ -1,5 +1,6 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
+use anyhow::bail;
 use clap::Parser;
 
 #[derive(Debug, Parser)]
@@ -10,13 +11,22 @@ pub struct Day2 {
 }
 
 impl Day2 {
+	pub const TITLE: &str = "Rock Paper Scissors";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let mut score1 = 0;
 		let mut score2 = 0;
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
-			let line = &line.as_bytes()[0..=2];
-			let l = i8::try_from(line[0])? - 'A' as i8 + 1;
-			let r = i8::try_from(line[2])? - 'X' as i8 + 1;
+		process_trimmed_nonempty_lines_of_file(input, |line| {
+			let (l, r) = match line.as_bytes() {
+				[l @ b'A'..=b'C', b' ', r @ b'X'..=b'Z', ..] => {
+					(i8::try_from(l - b'A')? + 1, i8::try_from(r - b'X')? + 1)
+				}
+				_ => bail!("invalid game: {line}"),
+			};
 			{
 				let op = l;
 				let sp = r;
@@ -12,6 +12,8 @@ pub struct Day3 {
 }
 
 impl Day3 {
+	pub const TITLE: &str = "Rucksack Reorganization";
+
 	fn priority_of(v: u8) -> anyhow::Result<u8> {
 		Ok(match v {
 			b'a'..=b'z' => v - b'a' + 1,
@@ -21,9 +23,12 @@ impl Day3 {
 	}
 
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_u8()?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		let input = input.as_bytes();
 		let lines: Vec<_> = input
-			.as_ref()
 			.split(|c| *c == b'\n')
 			.filter(|line| !line.is_empty())
 			.collect();
@@ -12,6 +12,8 @@ pub struct Day4 {
 }
 
 impl Day4 {
+	pub const TITLE: &str = "Camp Cleanup";
+
 	fn parse_part(part: &str) -> anyhow::Result<RangeInclusive<u8>> {
 		let (start, end) = part.split_once('-').context("missing `-` in part")?;
 		let start: u8 = start.parse()?;
@@ -20,8 +22,12 @@ impl Day4 {
 	}
 
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
 		let mut assignments = Vec::default();
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			let (first, second) = line.split_once(',').context("not a pair")?;
 			let first = Self::parse_part(first)?;
 			let second = Self::parse_part(second)?;
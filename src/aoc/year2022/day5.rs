@@ -1,10 +1,11 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 use itertools::Itertools;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use tracing::trace;
 
 #[derive(Debug, Parser)]
 pub struct Day5 {
@@ -41,23 +42,43 @@ impl FromStr for Move {
 			.flat_map(str::parse::<usize>)
 			.collect_tuple()
 			.with_context(|| format!("invalid move: {s}"))?;
-		let from = from - 1;
-		let to = to - 1;
+		let from = from.checked_sub(1).context("stacks are numbered from 1")?;
+		let to = to.checked_sub(1).context("stacks are numbered from 1")?;
 		Ok(Move { count, from, to })
 	}
 }
 
 impl Move {
-	fn perform(&self, stacks: &mut CrateStacks) {
-		assert!(self.to < stacks.0.len() && self.from < stacks.0.len());
+	/// Check that the move can be done on `stacks`.
+	fn check(&self, stacks: &CrateStacks) -> anyhow::Result<()> {
+		let from = stacks
+			.0
+			.get(self.from)
+			.with_context(|| format!("no stack to {self}"))?;
+		if self.to >= stacks.0.len() {
+			bail!("no stack to {self}");
+		}
+		if from.len() < self.count {
+			bail!("not enough crates to {self}");
+		}
+		Ok(())
+	}
+
+	fn perform(&self, stacks: &mut CrateStacks) -> anyhow::Result<()> {
+		self.check(stacks)?;
 		for _ in 0..self.count {
-			let crate_ = stacks.0[self.from].pop().unwrap();
+			let crate_ = stacks.0[self.from].pop().context("not enough crates")?;
 			stacks.0[self.to].push(crate_);
 		}
+		Ok(())
 	}
 
-	fn perform_concurrent(&self, stacks: &mut CrateStacks) {
-		assert!(self.to < stacks.0.len() && self.from < stacks.0.len());
+	fn perform_concurrent(&self, stacks: &mut CrateStacks) -> anyhow::Result<()> {
+		self.check(stacks)?;
+		if self.from == self.to {
+			// Lifting crates off a stack and putting them back in the same order changes nothing
+			return Ok(());
+		}
 		let (from, to) = if self.from < self.to {
 			let (left, right) = stacks.0.split_at_mut(self.from + 1);
 			(&mut left[self.from], &mut right[self.to - self.from - 1])
@@ -66,6 +87,7 @@ impl Move {
 			(&mut right[self.from - self.to - 1], &mut left[self.to])
 		};
 		to.extend(from.drain(from.len() - self.count..));
+		Ok(())
 	}
 }
 
@@ -74,7 +96,7 @@ struct CrateStacks(Vec<Vec<u8>>);
 
 impl Display for CrateStacks {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		let highest = self.0.iter().map(Vec::len).max().unwrap();
+		let highest = self.0.iter().map(Vec::len).max().unwrap_or(0);
 		for i in (0..highest).rev() {
 			for stack in &self.0 {
 				if i < stack.len() {
@@ -107,7 +129,7 @@ impl FromStr for CrateStacks {
 				let mut stack = Vec::with_capacity(32);
 				for crate_ in input_stacks
 					.clone()
-					.map(|l| l[idx])
+					.map(|l| l.get(idx).copied().unwrap_or(b' '))
 					.take_while(|&c| c != b' ')
 				{
 					stack.push(crate_);
@@ -120,10 +142,13 @@ impl FromStr for CrateStacks {
 }
 
 impl Day5 {
+	pub const TITLE: &str = "Supply Stacks";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(String, String)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref();
+		self.input.solve(Self::solve)
+	}
 
+	pub fn solve(input: &str) -> anyhow::Result<(String, String)> {
 		let (input_stacks, input_commands) = input
 			.split_once("\n\n")
 			.context("input has no blank line to split on")?;
@@ -136,11 +161,10 @@ impl Day5 {
 			.collect::<anyhow::Result<Vec<Move>>>()?;
 		let mut stacks2 = stacks.clone();
 
-		// println!("{stacks}");
+		trace!("Stacks:\n{stacks}");
 		for m in &moves {
-			// println!("{m} ->");
-			m.perform(&mut stacks);
-			// println!("{stacks}");
+			m.perform(&mut stacks)?;
+			trace!("After {m}:\n{stacks}");
 		}
 		let score1 = stacks
 			.0
@@ -148,11 +172,9 @@ impl Day5 {
 			.map(|s| Ok(*s.last().context("missing characters in stack1")? as char))
 			.collect::<anyhow::Result<String>>()?;
 
-		// println!("{stacks2}");
 		for m in &moves {
-			// println!("{m} ->");
-			m.perform_concurrent(&mut stacks2);
-			// println!("{stacks2}");
+			m.perform_concurrent(&mut stacks2)?;
+			trace!("After {m} moving the crates at once:\n{stacks2}");
 		}
 		let score2 = stacks2
 			.0
@@ -11,6 +11,8 @@ pub struct Day6 {
 }
 
 impl Day6 {
+	pub const TITLE: &str = "Tuning Trouble";
+
 	fn find_start_of_window(input: &str, window_size: usize) -> anyhow::Result<usize> {
 		Ok(window_size
 			+ input
@@ -25,8 +27,11 @@ impl Day6 {
 	}
 
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref().trim();
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		let input = input.trim();
 
 		let score1 = Self::find_start_of_window(input, 4)?;
 		let score2 = Self::find_start_of_window(input, 14)?;
@@ -14,9 +14,14 @@ pub struct Day7 {
 }
 
 impl Day7 {
+	pub const TITLE: &str = "No Space Left On Device";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_str()?;
-		let input = input.as_ref().trim();
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		let input = input.trim();
 
 		let mut size_stack = Vec::new();
 		let mut sizes = Vec::new();
@@ -81,7 +86,7 @@ impl Day7 {
 			.checked_sub(largest)
 			.context("somehow storing more than there is of free space")?;
 		let need = 30_000_000_usize.saturating_sub(free);
-		let idx = sizes.binary_search(&need).map_or_else(|i| i, |i| i);
+		let idx = sizes.binary_search(&need).unwrap_or_else(|i| i);
 		let score2 = sizes
 			.get(idx)
 			.copied()
@@ -1,17 +1,19 @@
 use crate::aoc::helpers::*;
+use crate::aoc::visualize::{Cell, Recorder, RenderArgs, Visualize};
 use crate::AocApp;
+use anyhow::bail;
 use clap::Parser;
-use itertools::Itertools;
 use std::fmt::Display;
-use std::io::Write;
 use std::ops::Range;
-use termcolor::{BufferWriter, Color, ColorSpec, WriteColor};
+use tracing::trace;
 
 #[derive(Debug, Parser)]
 pub struct Day8 {
 	/// The input file of "tree heights"
 	#[clap(default_value_t = DataFrom::internal(2022, 8))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub render: RenderArgs,
 }
 
 struct HeightMap {
@@ -19,22 +21,36 @@ struct HeightMap {
 	heights: Box<[u8]>,
 }
 
-impl From<&[u8]> for HeightMap {
-	fn from(mut input: &[u8]) -> Self {
+impl TryFrom<&[u8]> for HeightMap {
+	type Error = anyhow::Error;
+
+	fn try_from(mut input: &[u8]) -> anyhow::Result<Self> {
 		while input.last() == Some(&b'\n') {
 			input = &input[..input.len() - 1];
 		}
-		let width = input.iter().position(|&b| b == b'\n').unwrap();
-		let length = bytecount::count(input, b'\n') + 1;
-		let mut heights = vec![0; width * length].into_boxed_slice();
-		for (b, h) in input
+		let width = input
 			.iter()
-			.filter(|i| i.is_ascii_digit())
-			.zip_eq(heights.iter_mut())
-		{
-			*h = b - b'0';
+			.position(|&b| b == b'\n')
+			.unwrap_or(input.len());
+		if width == 0 {
+			bail!("input is empty");
 		}
-		HeightMap { width, heights }
+		let mut heights = Vec::with_capacity(input.len());
+		for (y, line) in input.split(|&b| b == b'\n').enumerate() {
+			if line.len() != width {
+				bail!("line {y} is {} trees wide instead of {width}", line.len());
+			}
+			for &b in line {
+				if !b.is_ascii_digit() {
+					bail!("invalid tree height on line {y}: {:?}", b as char);
+				}
+				heights.push(b - b'0');
+			}
+		}
+		Ok(HeightMap {
+			width,
+			heights: heights.into_boxed_slice(),
+		})
 	}
 }
 
@@ -88,54 +104,6 @@ impl HeightMap {
 			|| self.all_heights_below_or_eq(height, x..(x + 1), (y + 1)..l)
 	}
 
-	#[allow(dead_code)]
-	fn print_visible(&self) {
-		for y in 0..self.length() {
-			for x in 0..self.width() {
-				if self.is_visible(x, y) {
-					print!("🌲");
-				} else {
-					print!("  ");
-				}
-			}
-			println!();
-		}
-		println!();
-	}
-
-	const COLORS: [Color; 10] = [
-		Color::Rgb(0, 255 / 10, 0),
-		Color::Rgb(0, 255 / 9, 0),
-		Color::Rgb(0, 255 / 8, 0),
-		Color::Rgb(0, 255 / 7, 0),
-		Color::Rgb(0, 255 / 6, 0),
-		Color::Rgb(0, 255 / 5, 0),
-		Color::Rgb(0, 255 / 4, 0),
-		Color::Rgb(0, 255 / 3, 0),
-		Color::Rgb(0, 255 / 2, 0),
-		Color::Rgb(0, 255, 0),
-	];
-
-	#[allow(dead_code)]
-	fn print_trees(&self) -> anyhow::Result<()> {
-		let bufw = BufferWriter::stdout(termcolor::ColorChoice::Always);
-		for (i, &height) in self.heights.iter().enumerate() {
-			let mut buf = bufw.buffer();
-			if i % self.width == 0 {
-				writeln!(&mut buf)?;
-			}
-			buf.set_color(ColorSpec::new().set_fg(Some(Self::COLORS[height as usize])))
-				.unwrap();
-			write!(&mut buf, "X")?;
-			bufw.print(&buf)?;
-		}
-		let mut buf = bufw.buffer();
-		writeln!(&mut buf)?;
-		buf.reset()?;
-		bufw.print(&buf)?;
-		Ok(())
-	}
-
 	fn count_visible(&self) -> usize {
 		let (w, l) = self.size();
 		(0..l)
@@ -182,15 +150,42 @@ impl HeightMap {
 	}
 }
 
+impl Visualize for HeightMap {
+	fn size(&self) -> (usize, usize) {
+		self.size()
+	}
+
+	/// Visible trees are green and hidden ones grey, both brighter the taller they are.
+	fn cell(&self, x: usize, y: usize) -> Cell {
+		let height = self.get(x, y);
+		let shade = 255 / (10 - height);
+		let color = if self.is_visible(x, y) {
+			[0, shade, 0]
+		} else {
+			[shade / 2, shade / 2, shade / 2]
+		};
+		Cell::new((b'0' + height) as char, color)
+	}
+}
+
 impl Day8 {
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_u8()?;
-		let input = input.as_ref();
-
-		let map = HeightMap::from(input);
-		// println!("{}", &map);
-		// map.print_trees()?;
-		// map.print_visible();
+	pub const TITLE: &str = "Treetop Tree House";
+
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		let mut recorder = Recorder::new(app, &self.render, 2022, 8)?;
+		self.input
+			.solve(|input| Self::solve_recording(input, &mut recorder))
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		Self::solve_recording(input, &mut Recorder::off())
+	}
+
+	/// Solve both parts, drawing the trees to `recorder`.
+	fn solve_recording(input: &str, recorder: &mut Recorder) -> anyhow::Result<(usize, usize)> {
+		let map = HeightMap::try_from(input.as_bytes())?;
+		trace!("Tree heights:{map}");
+		recorder.frame(&map)?;
 
 		Ok((map.count_visible(), map.best_scenic_score()))
 	}
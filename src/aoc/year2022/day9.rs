@@ -1,4 +1,5 @@
 use crate::aoc::helpers::*;
+use crate::aoc::visualize::{Cell, Recorder, RenderArgs, Visualize};
 use crate::AocApp;
 use ahash::AHashSet;
 use anyhow::bail;
@@ -9,6 +10,8 @@ pub struct Day9 {
 	/// The input file of "movement commands"
 	#[clap(default_value_t = DataFrom::internal(2022, 9))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub render: RenderArgs,
 }
 
 struct State {
@@ -59,10 +62,60 @@ impl State {
 	}
 }
 
+/// The rope within the bounds of everywhere its head goes.
+struct RopeView<'s> {
+	state: &'s State,
+	min: (i16, i16),
+	max: (i16, i16),
+}
+
+impl Visualize for RopeView<'_> {
+	#[allow(clippy::cast_sign_loss)]
+	fn size(&self) -> (usize, usize) {
+		(
+			(self.max.0 - self.min.0 + 1) as usize,
+			(self.max.1 - self.min.1 + 1) as usize,
+		)
+	}
+
+	#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+	fn cell(&self, x: usize, y: usize) -> Cell {
+		// Up is positive y, so flip it to draw the top row first
+		let pos = (self.min.0 + x as i16, self.max.1 - y as i16);
+		if let Some(i) = self.state.knots.iter().position(|&knot| knot == pos) {
+			let glyph = if i == 0 {
+				'H'
+			} else {
+				(b'0' + i as u8) as char
+			};
+			Cell::new(glyph, [255, 255, 255])
+		} else if self.state.touched9.contains(&pos) {
+			Cell::new('#', [220, 60, 60])
+		} else if self.state.touched1.contains(&pos) {
+			Cell::new('#', [90, 90, 200])
+		} else {
+			Cell::new('.', [40, 40, 40])
+		}
+	}
+}
+
 impl Day9 {
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let mut state = State::default();
-		process_trimmed_nonempty_lines_of_file(&self.input, |line| {
+	pub const TITLE: &str = "Rope Bridge";
+
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(usize, usize)> {
+		let mut recorder = Recorder::new(app, &self.render, 2022, 9)?;
+		self.input
+			.solve(|input| Self::solve_recording(input, &mut recorder))
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		Self::solve_recording(input, &mut Recorder::off())
+	}
+
+	/// Solve both parts, drawing the rope after every move to `recorder`.
+	fn solve_recording(input: &str, recorder: &mut Recorder) -> anyhow::Result<(usize, usize)> {
+		let mut moves = Vec::new();
+		process_trimmed_nonempty_lines_of_file(input, |line| {
 			match line.as_bytes() {
 				[dir, b' ', count @ ..] => {
 					let dir = match dir {
@@ -77,17 +130,41 @@ impl Day9 {
 						.copied()
 						.map(|c| c - b'0')
 						.fold(0, |acc, c| acc * 10 + c as usize);
-					for _ in 0..count {
-						state.move_dir(dir);
-					}
+					moves.push((dir, count));
 				}
 				unhandled => bail!("unhandled line: {unhandled:?}"),
 			}
 			Ok(())
 		})?;
 
-		println!("Step 1: {}", state.touched1.len());
-		println!("Step 2: {}", state.touched9.len());
+		let mut state = State::default();
+		if recorder.is_recording() {
+			// The tail never leaves the bounds of the head, so those bound every frame
+			let (mut head, mut min, mut max) = ((0i16, 0i16), (0, 0), (0, 0));
+			for &((dx, dy), count) in &moves {
+				let count = i16::try_from(count)?;
+				head = (head.0 + dx * count, head.1 + dy * count);
+				min = (min.0.min(head.0), min.1.min(head.1));
+				max = (max.0.max(head.0), max.1.max(head.1));
+			}
+			for &(dir, count) in &moves {
+				for _ in 0..count {
+					state.move_dir(dir);
+				}
+				recorder.frame(&RopeView {
+					state: &state,
+					min,
+					max,
+				})?;
+			}
+		} else {
+			for &(dir, count) in &moves {
+				for _ in 0..count {
+					state.move_dir(dir);
+				}
+			}
+		}
+
 		Ok((state.touched1.len(), state.touched9.len()))
 	}
 }
@@ -15,9 +15,39 @@ pub mod day7;
 pub mod day8;
 pub mod day9;
 
+pub use day1::Day1;
+pub use day10::Day10;
+pub use day11::Day11;
+pub use day12::Day12;
+pub use day13::Day13;
+pub use day14::Day14;
+pub use day15::Day15;
+pub use day16::Day16;
+pub use day2::Day2;
+pub use day3::Day3;
+pub use day4::Day4;
+pub use day5::Day5;
+pub use day6::Day6;
+pub use day7::Day7;
+pub use day8::Day8;
+pub use day9::Day9;
+
+#[cfg(feature = "cli")]
 use crate::AocApp;
 use clap::Parser;
 
+/// The registered days of 2022, appended as a `[DayN, ...]` list to the arguments of the given
+/// macro, so everything generated for every day uses the same list.
+#[macro_export]
+macro_rules! year2022_days {
+	($($callback:ident)::+!($($args:tt)*)) => {
+		$($callback)::+! { $($args)* [
+			Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8, Day9, Day10, Day11, Day12, Day13, Day14,
+			Day15, Day16
+		] }
+	};
+}
+
 #[derive(Debug, Parser)]
 pub enum Year2022 {
 	/// Run all the Advent of Code 2022 days
@@ -72,27 +102,13 @@ pub enum Year2022 {
 	Day16(day16::Day16),
 }
 
+#[cfg(feature = "cli")]
 impl Year2022 {
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		crate::run_days!(
-			Year2022,
-			self,
-			app,
-			[
-				Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8, Day9, Day10, Day11, Day12, Day13,
-				Day14, Day15, Day16
-			]
-		)
+		crate::year2022_days!(crate::run_days!(Year2022, self, app,))
 	}
 
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
-		crate::run_all_days!(
-			Year2022,
-			app,
-			[
-				Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8, Day9, Day10, Day11, Day12, Day13,
-				Day14, Day15, Day16
-			]
-		)
+		crate::year2022_days!(crate::run_all_days!(Year2022, app,))
 	}
 }
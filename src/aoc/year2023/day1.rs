@@ -1,19 +1,45 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::bail;
-use clap::Parser;
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use regex::bytes::Regex;
 
 #[derive(Debug, Parser)]
 pub struct Day1 {
 	/// The input file of "calibration data"
 	#[clap(default_value_t = DataFrom::internal(2023, 1))]
 	pub input: DataFrom,
+	/// How to find the digits
+	#[clap(long = "impl", value_enum, default_value_t)]
+	pub implementation: Implementation,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Implementation {
+	/// Slice patterns over the bytes of each line, from either end
+	#[default]
+	Match,
+	/// A regex for the first digit and a reversed one, over the reversed line, for the last
+	Regex,
 }
 
 impl Day1 {
+	pub const TITLE: &str = "Trebuchet?!";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i32, i32)> {
+		self.input.solve(|input| match self.implementation {
+			Implementation::Match => Self::solve_match(input),
+			Implementation::Regex => Self::solve_regex(input),
+		})
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(i32, i32)> {
+		Self::solve_match(input)
+	}
+
+	fn solve_match(input: &str) -> anyhow::Result<(i32, i32)> {
 		let (step1, step2) = fold_trimmed_nonempty_lines_of_file_bytes(
-			&self.input,
+			input,
 			(0i32, 0i32),
 			|(acc1, acc2), line| {
 				let step1 = {
@@ -94,4 +120,46 @@ impl Day1 {
 
 		Ok((step1, step2))
 	}
+
+	fn solve_regex(input: &str) -> anyhow::Result<(i32, i32)> {
+		const NAMES: [&str; 10] = [
+			"zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+		];
+		let digit = Regex::new(r"\d")?;
+		let first = Regex::new(&format!(r"\d|{}", NAMES.join("|")))?;
+		let reversed: Vec<String> = NAMES.iter().map(|n| n.chars().rev().collect()).collect();
+		let last = Regex::new(&format!(r"\d|{}", reversed.join("|")))?;
+		let value = |found: &[u8], reversed: bool| -> i32 {
+			match found {
+				[d] => i32::from(d - b'0'),
+				name => (0..)
+					.zip(NAMES)
+					.find(|(_, n)| {
+						n.len() == name.len()
+							&& if reversed {
+								n.bytes().rev().eq(name.iter().copied())
+							} else {
+								n.as_bytes() == name
+							}
+					})
+					.map_or(0, |(d, _)| d),
+			}
+		};
+
+		fold_trimmed_nonempty_lines_of_file_bytes(input, (0, 0), |(acc1, acc2), line| {
+			let no_number = || format!("No number found in line: {:?}", std::str::from_utf8(line));
+			let rev_line: Vec<u8> = line.iter().rev().copied().collect();
+			let step1 = {
+				let first = digit.find(line).with_context(no_number)?;
+				let last = digit.find(&rev_line).with_context(no_number)?;
+				value(first.as_bytes(), false) * 10 + value(last.as_bytes(), true)
+			};
+			let step2 = {
+				let first = first.find(line).with_context(no_number)?;
+				let last = last.find(&rev_line).with_context(no_number)?;
+				value(first.as_bytes(), false) * 10 + value(last.as_bytes(), true)
+			};
+			Ok((acc1 + step1, acc2 + step2))
+		})
+	}
 }
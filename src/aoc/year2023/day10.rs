@@ -1,24 +1,39 @@
 use crate::aoc::helpers::*;
+use crate::aoc::visualize::{Cell, Recorder, RenderArgs, Visualize};
 use crate::AocApp;
 use anyhow::{bail, Context};
 use clap::Parser;
 use std::fmt::{Display, Formatter, Write};
+use tracing::debug;
 
 #[derive(Debug, Parser)]
 pub struct Day10 {
 	/// The input file of "pipes"
 	#[clap(default_value_t = DataFrom::internal(2023, 10))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub render: RenderArgs,
 }
 
 impl Day10 {
+	pub const TITLE: &str = "Pipe Maze";
+
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<(usize, usize)> {
-		let input = self.input.as_cow_u8()?;
-		let map = Map::new(&input)?;
+		let mut recorder = Recorder::new(app, &self.render, 2023, 10)?;
+		self.input
+			.solve(|input| Self::solve_recording(input, &mut recorder))
+	}
 
-		if app.verbose >= 2 {
-			println!("{map}");
-		}
+	pub fn solve(input: &str) -> anyhow::Result<(usize, usize)> {
+		Self::solve_recording(input, &mut Recorder::off())
+	}
+
+	/// Solve both parts, drawing the pipes with the tiles inside the loop marked to `recorder`.
+	fn solve_recording(input: &str, recorder: &mut Recorder) -> anyhow::Result<(usize, usize)> {
+		let map = Map::new(input.as_bytes())?;
+
+		debug!("The pipes, with the tiles inside the loop marked:\n{map}");
+		recorder.frame(&map)?;
 
 		let score1 = map.start_loop.len() / 2;
 		let score2 = map.count_inside;
@@ -134,6 +149,7 @@ enum CacheSide {
 	Right(Coord),
 }
 
+#[allow(clippy::struct_field_names)]
 struct Map<'s> {
 	width: usize,
 	// height: usize,
@@ -144,7 +160,7 @@ struct Map<'s> {
 	count_inside: usize,
 }
 
-impl<'s> Display for Map<'s> {
+impl Display for Map<'_> {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		for y in 0..self.map.len() / self.width {
 			// let start = y * self.width;
@@ -179,6 +195,27 @@ impl<'s> Display for Map<'s> {
 	}
 }
 
+impl Visualize for Map<'_> {
+	fn size(&self) -> (usize, usize) {
+		// The width includes the newline at the end of each line
+		(self.width - 1, self.map.len() / self.width)
+	}
+
+	fn cell(&self, x: usize, y: usize) -> Cell {
+		match self.annotated_map[y * self.width + x] {
+			b' ' => Cell::new('.', [60, 60, 60]),
+			b'#' => Cell::new('#', [80, 200, 80]),
+			b'J' => Cell::new('╯', [230, 230, 230]),
+			b'L' => Cell::new('╰', [230, 230, 230]),
+			b'7' => Cell::new('╮', [230, 230, 230]),
+			b'F' => Cell::new('╭', [230, 230, 230]),
+			b'|' => Cell::new('│', [230, 230, 230]),
+			b'-' => Cell::new('─', [230, 230, 230]),
+			c => Cell::new(c as char, [230, 60, 60]),
+		}
+	}
+}
+
 impl<'s> Map<'s> {
 	fn coord_to_idx(&self, coord: Coord) -> usize {
 		(coord.1 as usize) * self.width + (coord.0 as usize)
@@ -221,6 +258,31 @@ impl<'s> Map<'s> {
 		Ok(looped)
 	}
 
+	/// The pipe hidden under the start, from the two pipes of the loop on either side of it.
+	fn start_pipe(&self) -> anyhow::Result<Pipe> {
+		let start = self.idx_to_coord(self.start);
+		let (Some(&next), Some(&prior)) = (self.start_loop.get(1), self.start_loop.last()) else {
+			bail!("loop from the start at {start:?} is too short");
+		};
+		let connects = |to: Coord| next == to || prior == to;
+		Ok(
+			match (
+				connects(start.up()),
+				connects(start.down()),
+				connects(start.left()),
+				connects(start.right()),
+			) {
+				(true, true, _, _) => Pipe::Vertical,
+				(_, _, true, true) => Pipe::Horizontal,
+				(true, _, true, _) => Pipe::TopLeft,
+				(true, _, _, true) => Pipe::TopRight,
+				(_, true, true, _) => Pipe::BottomLeft,
+				(_, true, _, true) => Pipe::BottomRight,
+				_ => bail!("start at {start:?} doesn't join {prior:?} and {next:?}"),
+			},
+		)
+	}
+
 	#[allow(clippy::too_many_lines)]
 	pub fn new(map_data: &'s [u8]) -> anyhow::Result<Self> {
 		let width = map_data
@@ -228,7 +290,7 @@ impl<'s> Map<'s> {
 			.position(|&b| b == b'\n')
 			.context("input has no newlines")?
 			+ 1;
-		if map_data.len() % width != 0 {
+		if !map_data.len().is_multiple_of(width) {
 			bail!("input is not a rectangle");
 		}
 		let height = map_data.len() / width;
@@ -253,13 +315,15 @@ impl<'s> Map<'s> {
 			let idx = map.coord_to_idx(coord);
 			map.annotated_map[idx] = map.map[idx];
 		}
+		// The fill needs to know which way the start turns, just like any other pipe
+		map.annotated_map[map.start] = map.start_pipe()? as u8;
 		let first_pipe = map.idx_to_coord(
 			map.annotated_map
 				.iter()
 				.position(|&b| b != b' ')
 				.context("annotated map is empty")?,
 		);
-		if ![Pipe::BottomRight, Pipe::Start].contains(&first_pipe.get_pipe(&map)) {
+		if map.annotated_map[map.coord_to_idx(first_pipe)] != Pipe::BottomRight as u8 {
 			bail!("first pipe is not a bottom-right corner at {first_pipe:?}, malformed annotated map:\n{map}");
 		}
 		map.count_inside = map.flood_fill(CacheSide::DR(first_pipe));
@@ -279,7 +343,10 @@ impl<'s> Map<'s> {
 				| Left(coord) | Right(coord) => coord,
 			};
 			let idx = self.coord_to_idx(coord);
-			let tile = &mut self.annotated_map[idx];
+			// Only a malformed map lets the fill reach past its edges
+			let Some(tile) = self.annotated_map.get_mut(idx) else {
+				continue;
+			};
 			if done[idx] {
 				continue;
 			}
@@ -363,9 +430,6 @@ impl<'s> Map<'s> {
 					]);
 				}
 				b'7' if matches!(entry, DL(_)) => cache.push(UR(coord.down().left())),
-				b'S' if matches!(entry, DR(_)) => {
-					cache.push(UL(coord.down().right()));
-				}
 				_ => {}
 			}
 		}
@@ -375,33 +439,5 @@ impl<'s> Map<'s> {
 
 #[cfg(test)]
 mod tests {
-	crate::run_basic_tests!(
-		super::Day10,
-		simple_example: (".....
-.S-7.
-.|.|.
-.L-J.
-.....
-", (4, 1),
-		),
-		busy_example: ("-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF
-", (4, 1),
-		),
-		count_inside: ("FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L
-", (80, 10),
-		),
-	);
+	crate::run_basic_tests!(super::Day10, examples: (2023, 10));
 }
@@ -14,8 +14,14 @@ const MILLION: i64 = 1_000_000;
 
 #[allow(clippy::similar_names)]
 impl Day11 {
+	pub const TITLE: &str = "Cosmic Expansion";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i64, i64)> {
-		let input = self.input.as_cow_u8()?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(i64, i64)> {
+		let input = input.as_bytes();
 		let width = input
 			.iter()
 			.position(|&b| b == b'\n')
@@ -80,7 +86,7 @@ mod tests {
 .........#
 ..........
 .......#..
-#...#.....", (374, 82000210),
+#...#.....", (374, 82_000_210),
 		),
 	);
 }
@@ -13,11 +13,17 @@ pub struct Day12 {
 }
 
 impl Day12 {
+	pub const TITLE: &str = "Hot Springs";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
 		let mut cache = AHashMap::with_capacity(512);
 
 		let score1 = {
-			let entries = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
+			let entries = map_trimmed_nonempty_lines_of_file(input, |line| {
 				let (states, rle) = line
 					.split_once(' ')
 					.context("line missing a single space")?;
@@ -50,7 +56,7 @@ impl Day12 {
 		};
 
 		let score2 = {
-			let entries = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
+			let entries = map_trimmed_nonempty_lines_of_file(input, |line| {
 				let (states, rle) = line
 					.split_once(' ')
 					.context("line missing a single space")?;
@@ -84,6 +90,11 @@ impl Day12 {
 					}
 				}
 
+				// Indices into the row must fit the cache key
+				if states.len() >= u16::MAX.into() {
+					bail!("row is too long: {} springs", states.len());
+				}
+
 				Ok(SpringRow { states, rle })
 			})?;
 
@@ -103,8 +114,9 @@ impl Day12 {
 type StateArray = Vec<SpringState>;
 type RleArray = Vec<u8>;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
 enum SpringState {
+	#[default]
 	Unknown,
 	Bad,
 	Good,
@@ -120,12 +132,6 @@ impl Debug for SpringState {
 	}
 }
 
-impl Default for SpringState {
-	fn default() -> Self {
-		Self::Unknown
-	}
-}
-
 #[derive(Debug, Clone)]
 struct SpringRow {
 	states: StateArray,
@@ -135,12 +141,12 @@ struct SpringRow {
 impl SpringRow {
 	fn count_possibilities_cached(
 		&self,
-		cache: &mut AHashMap<u32, u64>,
-		si: u8,
-		ri: u8,
-		existing_rle_value: u8,
+		cache: &mut AHashMap<u64, u64>,
+		si: u16,
+		ri: u16,
+		existing_rle_value: u16,
 	) -> u64 {
-		let key = u32::from_ne_bytes([si, ri, existing_rle_value, 0]);
+		let key = u64::from(si) << 32 | u64::from(ri) << 16 | u64::from(existing_rle_value);
 		if let Some(&res) = cache.get(&key) {
 			return res;
 		}
@@ -148,7 +154,9 @@ impl SpringRow {
 			if ri as usize == self.rle.len() {
 				return 1;
 			}
-			if ri as usize == self.rle.len() - 1 && self.rle[ri as usize] == existing_rle_value {
+			if ri as usize == self.rle.len() - 1
+				&& u16::from(self.rle[ri as usize]) == existing_rle_value
+			{
 				return 1;
 			}
 			return 0;
@@ -158,7 +166,8 @@ impl SpringRow {
 		if matches!(state, SpringState::Good | SpringState::Unknown) {
 			if existing_rle_value == 0 {
 				count += self.count_possibilities_cached(cache, si + 1, ri, 0);
-			} else if self.rle.get(ri as usize).copied() == Some(existing_rle_value) {
+			} else if self.rle.get(ri as usize).copied().map(u16::from) == Some(existing_rle_value)
+			{
 				count += self.count_possibilities_cached(cache, si + 1, ri + 1, 0);
 			}
 		}
@@ -174,25 +183,78 @@ impl SpringRow {
 
 #[cfg(test)]
 mod tests {
-	crate::run_basic_tests!(
-		super::Day12,
-		trivial_example: (
-			"#.#.### 1,1,3
-.#...#....###. 1,1,3
-.#.###.#.###### 1,3,1,6
-####.#...#... 4,1,1
-#....######..#####. 1,6,5
-.###.##....# 3,2,1
-", (6, 6),
-		),
-		main_example: (
-			"???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1
-", (21, 525_152)
-		),
-	);
+	use super::{SpringRow, SpringState};
+	use ahash::AHashMap;
+	use proptest::prelude::*;
+
+	crate::run_basic_tests!(super::Day12, examples: (2023, 12));
+
+	/// Try every way of filling in the unknown springs, counting those that match the groups.
+	fn count_brute_force(row: &SpringRow) -> u64 {
+		let unknowns: Vec<usize> = (0..row.states.len())
+			.filter(|&i| row.states[i] == SpringState::Unknown)
+			.collect();
+		let mut count = 0;
+		for bits in 0..1u32 << unknowns.len() {
+			let mut states = row.states.clone();
+			for (i, &idx) in unknowns.iter().enumerate() {
+				states[idx] = if (bits >> i) & 1 == 1 {
+					SpringState::Bad
+				} else {
+					SpringState::Good
+				};
+			}
+			let groups: Vec<u8> = states
+				.split(|&s| s == SpringState::Good)
+				.filter(|group| !group.is_empty())
+				.map(|group| u8::try_from(group.len()).unwrap())
+				.collect();
+			if groups == row.rle {
+				count += 1;
+			}
+		}
+		count
+	}
+
+	/// A row built from its groups, so it has at least one arrangement, with some springs then
+	/// made unknown.
+	fn spring_row() -> impl Strategy<Value = SpringRow> {
+		(
+			0..3usize,
+			prop::collection::vec((1..4u8, 1..3usize), 1..5),
+			0..3usize,
+		)
+			.prop_flat_map(|(leading, groups, trailing)| {
+				let mut states = vec![SpringState::Good; leading];
+				for (i, &(len, gap)) in groups.iter().enumerate() {
+					if i > 0 {
+						states.extend(std::iter::repeat_n(SpringState::Good, gap));
+					}
+					states.extend(std::iter::repeat_n(SpringState::Bad, len.into()));
+				}
+				states.extend(std::iter::repeat_n(SpringState::Good, trailing));
+				let rle = groups.iter().map(|&(len, _)| len).collect();
+				let unknown = prop::collection::vec(any::<bool>(), states.len());
+				(Just(states), Just(rle), unknown)
+			})
+			.prop_map(|(mut states, rle, unknown)| {
+				for (state, unknown) in states.iter_mut().zip(unknown) {
+					if unknown {
+						*state = SpringState::Unknown;
+					}
+				}
+				SpringRow { states, rle }
+			})
+	}
+
+	proptest! {
+		#[test]
+		fn count_possibilities_matches_brute_force(row in spring_row()) {
+			let mut cache = AHashMap::new();
+			prop_assert_eq!(
+				row.count_possibilities_cached(&mut cache, 0, 0, 0),
+				count_brute_force(&row)
+			);
+		}
+	}
 }
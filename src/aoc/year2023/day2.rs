@@ -11,12 +11,18 @@ pub struct Day2 {
 }
 
 impl Day2 {
+	pub const TITLE: &str = "Cube Conundrum";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
 		// (red, green, blue)
 		let step1_max_color_counts = (12, 13, 14);
 		let mut score1 = 0;
 		let mut score2 = 0;
-		process_trimmed_nonempty_lines_of_file(&self.input, |mut line| {
+		process_trimmed_nonempty_lines_of_file(input, |mut line| {
 			let orig_line = line;
 			line = line
 				.strip_prefix("Game ")
@@ -1,7 +1,8 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
+use std::borrow::Cow;
 
 #[derive(Debug, Parser)]
 pub struct Day3 {
@@ -11,13 +12,36 @@ pub struct Day3 {
 }
 
 impl Day3 {
+	pub const TITLE: &str = "Gear Ratios";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u32, u32)> {
-		let data = self.input.as_cow_u8()?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u32, u32)> {
+		let mut data = Cow::Borrowed(input.as_bytes());
+		if data.last() != Some(&b'\n') {
+			data.to_mut().push(b'\n');
+		}
 		let stride = data
 			.iter()
 			.position(|&b| b == b'\n')
 			.context("no newlines found in input")?
 			+ 1;
+		// The neighbours are found by the stride, so every row has to be as wide
+		if !data.len().is_multiple_of(stride)
+			|| data
+				.iter()
+				.enumerate()
+				.any(|(i, &b)| (b == b'\n') != (i % stride == stride - 1))
+		{
+			bail!(AssumptionFailed("rows are not all as wide".to_string()));
+		}
+		if data.split(|b| !b.is_ascii_digit()).any(|n| n.len() > 3) {
+			bail!(AssumptionFailed(
+				"numbers have more than 3 digits".to_string()
+			));
+		}
 
 		let mut score1 = 0;
 		let mut score2 = 0;
@@ -56,7 +80,7 @@ fn is_part(data: &[u8], left: usize, stride: usize) -> Option<&[u8]> {
 		.position(|b| !b.is_ascii_digit())
 		.unwrap_or(data.len() + 1)
 		- 1 + left;
-	let do_left = left % stride != 0;
+	let do_left = !left.is_multiple_of(stride);
 	let do_right = right % stride != stride - 1;
 	let do_top = left >= stride;
 	let do_bottom = left + stride < data.len();
@@ -100,7 +124,7 @@ fn is_part(data: &[u8], left: usize, stride: usize) -> Option<&[u8]> {
 }
 
 fn get_dual_gear_score(data: &[u8], gear: usize, stride: usize) -> Option<u32> {
-	let do_left = gear % stride != 0;
+	let do_left = !gear.is_multiple_of(stride);
 	let do_right = gear % stride != stride - 1;
 	let do_top = gear >= stride;
 	let do_bottom = gear + stride < data.len();
@@ -137,14 +161,20 @@ fn get_dual_gear_score(data: &[u8], gear: usize, stride: usize) -> Option<u32> {
 			}
 		} else {
 			// Middle is not a number, so test both corners instead
-			if let Some(value) = get_number_at(data, gear - stride - 1) {
+			if let Some(value) = do_left
+				.then(|| get_number_at(data, gear - stride - 1))
+				.flatten()
+			{
 				count += 1;
 				score *= value;
 				if count > 2 {
 					return None;
 				}
 			}
-			if let Some(value) = get_number_at(data, gear - stride + 1) {
+			if let Some(value) = do_right
+				.then(|| get_number_at(data, gear - stride + 1))
+				.flatten()
+			{
 				count += 1;
 				score *= value;
 				if count > 2 {
@@ -163,14 +193,20 @@ fn get_dual_gear_score(data: &[u8], gear: usize, stride: usize) -> Option<u32> {
 			}
 		} else {
 			// Middle is not a number, so test both corners instead
-			if let Some(value) = get_number_at(data, gear + stride - 1) {
+			if let Some(value) = do_left
+				.then(|| get_number_at(data, gear + stride - 1))
+				.flatten()
+			{
 				count += 1;
 				score *= value;
 				if count > 2 {
 					return None;
 				}
 			}
-			if let Some(value) = get_number_at(data, gear + stride + 1) {
+			if let Some(value) = do_right
+				.then(|| get_number_at(data, gear + stride + 1))
+				.flatten()
+			{
 				count += 1;
 				score *= value;
 				if count > 2 {
@@ -196,7 +232,7 @@ fn get_number_at(data: &[u8], mid: usize) -> Option<u32> {
 		left -= 1;
 	}
 	let mut right = mid;
-	while right < data.len() && data[right + 1].is_ascii_digit() {
+	while right + 1 < data.len() && data[right + 1].is_ascii_digit() {
 		right += 1;
 	}
 	let value = data[left..=right]
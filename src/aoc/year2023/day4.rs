@@ -12,11 +12,17 @@ pub struct Day4 {
 }
 
 impl Day4 {
+	pub const TITLE: &str = "Scratchcards";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i32, i32)> {
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(i32, i32)> {
 		let mut extras = VecDeque::with_capacity(128);
 		let mut score1 = 0;
 		let mut score2 = 0;
-		process_trimmed_nonempty_lines_of_file(&self.input, |mut line| {
+		process_trimmed_nonempty_lines_of_file(input, |mut line| {
 			line = line
 				.strip_prefix("Card ")
 				.context("missing `Card ` prefix")?;
@@ -40,7 +46,7 @@ impl Day4 {
 			score1 += score;
 			let cur_count = 1 + extras.pop_front().unwrap_or_default();
 			if extras.len() < count {
-				extras.extend(std::iter::repeat(0).take(count - extras.len()));
+				extras.extend(std::iter::repeat_n(0, count - extras.len()));
 			}
 			extras
 				.iter_mut()
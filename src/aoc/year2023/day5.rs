@@ -16,8 +16,13 @@ pub struct Day5 {
 }
 
 impl Day5 {
+	pub const TITLE: &str = "If You Give A Seed A Fertilizer";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
-		let input = self.input.as_cow_str()?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
 		let input = input.trim();
 		let mut seeds = Vec::with_capacity(32);
 		let mut seed_ranges = Vec::with_capacity(16);
@@ -150,7 +155,7 @@ struct Mapping<'s> {
 	to: &'s str,
 	redirects: Vec<Redirect>,
 }
-impl<'s> Mapping<'s> {
+impl Mapping<'_> {
 	pub fn map_id(&self, id: u64) -> u64 {
 		for redirect in &self.redirects {
 			if let Some(dest) = redirect.map_id(id) {
@@ -196,8 +201,8 @@ impl Redirect {
 				*id = *self.source.start()..=*id.end();
 			}
 		}
-		if id.start() < self.source.end() {
-			if id.end() < self.source.end() {
+		if id.start() <= self.source.end() {
+			if id.end() <= self.source.end() {
 				out.push(
 					*self.dest.start() + id.start() - self.source.start()
 						..=*self.dest.start() + id.end() - self.source.start(),
@@ -213,3 +218,47 @@ impl Redirect {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{Mapping, Redirect};
+	use proptest::prelude::*;
+
+	/// Redirects sorted by their non-overlapping sources, as the input gives them.
+	fn mapping() -> impl Strategy<Value = Mapping<'static>> {
+		prop::collection::vec((0..5u64, 1..6u64, 0..50u64), 0..6).prop_map(|redirects| {
+			let mut start = 0;
+			let redirects = redirects
+				.into_iter()
+				.map(|(gap, length, dest)| {
+					start += gap;
+					let source = start..=start + length - 1;
+					start += length;
+					Redirect {
+						source,
+						dest: dest..=dest + length - 1,
+					}
+				})
+				.collect();
+			Mapping {
+				from: "a",
+				to: "b",
+				redirects,
+			}
+		})
+	}
+
+	proptest! {
+		#[test]
+		fn map_id_range_matches_map_id(mapping in mapping(), start in 0..50u64, length in 1..20u64) {
+			let ids = start..=start + length - 1;
+			let mut expected: Vec<u64> = ids.clone().map(|id| mapping.map_id(id)).collect();
+			let mut ranges = Vec::new();
+			mapping.map_id_range(ids, &mut ranges);
+			let mut mapped: Vec<u64> = ranges.into_iter().flatten().collect();
+			expected.sort_unstable();
+			mapped.sort_unstable();
+			prop_assert_eq!(mapped, expected);
+		}
+	}
+}
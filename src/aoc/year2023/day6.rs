@@ -13,8 +13,13 @@ pub struct Day6 {
 }
 
 impl Day6 {
+	pub const TITLE: &str = "Wait For It";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
-		let input = self.input.as_cow_str()?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
 		let (time_line, dist_line) = input
 			.split_once('\n')
 			.context("input must have two lines")?;
@@ -89,56 +94,76 @@ impl Race {
 			let high = (0..self.time)
 				.rfind(|&held| self.is_record_when(held))
 				.with_context(|| {
-					format!("found no high winning range for {self:?} (low = {low})",)
+					format!("found no high winning range for {self:?} (low = {low})")
 				})?;
 			return Ok(low..=high);
 		}
 
-		let mut low = 0;
-		let mut high = self.time;
-		let mut mid = low + (high - low) / 2;
-		if !self.is_record_when(mid) {
-			bail!("mid point is not in the winning range for {self:?} (mid = {mid})");
+		// The distance rises until holding for half the time then falls, so bisect either side
+		let peak = self.time / 2;
+		if !self.is_record_when(peak) {
+			bail!("peak is not in the winning range for {self:?} (peak = {peak})");
 		}
 
-		// get low bound
-		let low = if self.is_record_when(low) {
-			low
-		} else {
-			loop {
-				if mid == low {
-					break mid + 1;
-				} else if self.is_record_when(mid) {
-					if mid == low + 1 {
-						break mid;
-					}
-					mid -= (mid - low) / 2;
+		// get low bound, the first winning hold time up to the peak
+		let low = {
+			let (mut from, mut to) = (0, peak);
+			while from < to {
+				let mid = from + (to - from) / 2;
+				if self.is_record_when(mid) {
+					to = mid;
 				} else {
-					low = mid;
-					mid += (high - mid) / 2;
+					from = mid + 1;
 				}
 			}
+			from
 		};
 
-		// get high bound
-		let high = if self.is_record_when(high) {
-			high
-		} else {
-			loop {
-				if mid == high {
-					break mid - 1;
-				} else if self.is_record_when(mid) {
-					if mid == high - 1 {
-						break mid;
-					}
-					mid += (high - mid) / 2;
+		// get high bound, the last winning hold time from the peak
+		let high = {
+			let (mut from, mut to) = (peak, self.time);
+			while from < to {
+				let mid = from + (to - from).div_ceil(2);
+				if self.is_record_when(mid) {
+					from = mid;
 				} else {
-					high = mid;
-					mid -= (mid - low) / 2;
+					to = mid - 1;
 				}
 			}
+			from
 		};
 
 		Ok(low..=high)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::Race;
+	use proptest::prelude::*;
+	use std::ops::RangeInclusive;
+
+	/// Check every hold time.
+	fn winning_range_brute_force(race: &Race) -> RangeInclusive<u64> {
+		let low = (0..=race.time).find(|&held| race.is_record_when(held));
+		let high = (0..=race.time).rfind(|&held| race.is_record_when(held));
+		low.unwrap()..=high.unwrap()
+	}
+
+	proptest! {
+		// Times past `u16::MAX` take the bisecting path
+		#[test]
+		fn winning_range_matches_brute_force(
+			time in 2..200_000u64,
+			record in any::<prop::sample::Index>(),
+		) {
+			// Holding for half the time goes the furthest, so a lower record can always be beaten
+			let best = (time / 2) * (time - time / 2);
+			let race = Race {
+				time,
+				record_distance: record.index(usize::try_from(best).unwrap()) as u64,
+			};
+			prop_assert_eq!(race.get_winning_range().unwrap(), winning_range_brute_force(&race));
+		}
+	}
+}
@@ -1,8 +1,9 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::bail;
+use anyhow::{bail, Context};
 use clap::Parser;
 use itertools::Itertools;
+use tracing::trace;
 
 #[derive(Debug, Parser)]
 pub struct Day7 {
@@ -12,13 +13,22 @@ pub struct Day7 {
 }
 
 impl Day7 {
+	pub const TITLE: &str = "Camel Cards";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
-		let input = self.input.as_cow_str()?;
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
 		let mut hands = Vec::with_capacity(512);
-		for hand in input.trim().split('\n').map(|line| {
+		for hand in input.trim().lines().map(|line| {
 			if let Some((cards, bid)) = line.trim().split_once(' ') {
 				let mut card_types = [CardValue::Two; 5];
-				for (idx, card) in cards.as_bytes()[..5].iter().copied().enumerate() {
+				let cards = cards
+					.as_bytes()
+					.get(..5)
+					.with_context(|| format!("Invalid hand: {cards}"))?;
+				for (idx, card) in cards.iter().copied().enumerate() {
 					card_types[idx] = card.try_into()?;
 				}
 				let bid = bid.parse::<u64>()?;
@@ -32,19 +42,7 @@ impl Day7 {
 
 		hands.sort_by(Hand::cmp_rank);
 
-		// let mut final_count = 0;
-		// for (rank, hand) in hands.iter().enumerate() {
-		// 	let rank = rank as u64 + 1;
-		// 	final_count += rank * hand.bid;
-		// 	println!(
-		// 		"{rank}: {:?} -> {:?} -> {} -> Score: {}",
-		// 		hand.highest_type,
-		// 		hand.cards.iter().format(" "),
-		// 		hand.bid,
-		// 		rank * hand.bid,
-		// 	);
-		// }
-		// println!("Final score: {final_count}");
+		trace_ranking(&hands);
 
 		let score1 = hands
 			.iter()
@@ -54,19 +52,7 @@ impl Day7 {
 
 		hands.iter_mut().for_each(Hand::convert_jacks_to_jokers);
 		hands.sort_by(Hand::cmp_rank);
-		// let mut final_count = 0;
-		// for (rank, hand) in hands.iter().enumerate() {
-		// 	let rank = rank as u64 + 1;
-		// 	final_count += rank * hand.bid;
-		// 	println!(
-		// 		"{rank}: {:?} -> {:?} -> {} -> Score: {}",
-		// 		hand.highest_type,
-		// 		hand.cards.iter().format(" "),
-		// 		hand.bid,
-		// 		rank * hand.bid,
-		// 	);
-		// }
-		// println!("Final score: {final_count}");
+		trace_ranking(&hands);
 		let score2 = hands
 			.iter()
 			.enumerate()
@@ -77,6 +63,25 @@ impl Day7 {
 	}
 }
 
+fn trace_ranking(hands: &[Hand]) {
+	if !tracing::enabled!(tracing::Level::TRACE) {
+		return;
+	}
+	let mut final_count = 0;
+	for (rank, hand) in hands.iter().enumerate() {
+		let rank = rank as u64 + 1;
+		final_count += rank * hand.bid;
+		trace!(
+			"{rank}: {:?} -> {:?} -> {} -> Score: {}",
+			hand.highest_type,
+			hand.cards.iter().format(" "),
+			hand.bid,
+			rank * hand.bid,
+		);
+	}
+	trace!("Final score: {final_count}");
+}
+
 #[derive(Debug)]
 struct Hand {
 	cards: [CardValue; 5],
@@ -1,3 +1,4 @@
+use crate::aoc::graph_export::{ExportGraph, GraphArgs};
 use crate::aoc::helpers::*;
 use crate::AocApp;
 use anyhow::{bail, Context};
@@ -11,12 +12,33 @@ pub struct Day8 {
 	/// The input file of "map and direction" data
 	#[clap(default_value_t = DataFrom::internal(2023, 8))]
 	pub input: DataFrom,
+	#[clap(flatten)]
+	pub graph: GraphArgs,
 }
 
 impl Day8 {
+	pub const TITLE: &str = "Haunted Wasteland";
+
+	pub fn run(&self, app: &AocApp) -> anyhow::Result<(u64, u64)> {
+		let (scores, export) = self
+			.input
+			.solve(|input| Self::solve_exporting(input, self.graph.export_graph.is_some()))?;
+		if let Some(export) = export {
+			export.export(app, &self.graph, 2023, 8)?;
+		}
+		Ok(scores)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(u64, u64)> {
+		Ok(Self::solve_exporting(input, false)?.0)
+	}
+
+	/// Solve both parts, and with `export` also build the graph of the map to export.
 	#[allow(clippy::too_many_lines)]
-	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(u64, u64)> {
-		let input = self.input.as_cow_str()?;
+	fn solve_exporting(
+		input: &str,
+		export: bool,
+	) -> anyhow::Result<((u64, u64), Option<ExportGraph>)> {
 		let (directions, map_lines) = input
 			.split_once("\n\n")
 			.with_context(|| "Failed to split input into directions and map from:\n{input}")?;
@@ -53,6 +75,10 @@ impl Day8 {
 		// let mut current = *b"AAA";
 		let mut current = map.get_index_of(b"AAA").context("invalid map key: AAA")?;
 		let destination = map.get_index_of(b"ZZZ").context("invalid map key: AAA")?;
+		let mut path1 = Vec::new();
+		if export {
+			path1.push(current);
+		}
 		for dir in directions.iter().copied().cycle() {
 			score1 += 1;
 			let entry = map.get_index(current).with_context(|| {
@@ -66,11 +92,33 @@ impl Day8 {
 				b'R' => entry.1 .1,
 				_ => bail!("invalid direction: {dir}"),
 			};
+			if export {
+				path1.push(current);
+			}
 			if current == destination {
 				break;
 			}
 		}
 
+		// Highlight the step 1 path, and the start and end nodes of step 2
+		let export = export.then(|| {
+			let mut export = ExportGraph::new();
+			for key in map.keys() {
+				export.add_node(String::from_utf8_lossy(key), "");
+			}
+			for (idx, &(left, right)) in map.values().enumerate() {
+				export.add_edge(idx, left, "L");
+				export.add_edge(idx, right, "R");
+			}
+			export.highlight_path(&path1);
+			for (idx, key) in map.keys().enumerate() {
+				if matches!(key[2], b'A' | b'Z') {
+					export.highlight_node(idx);
+				}
+			}
+			export
+		});
+
 		let mut currents: Vec<_> = map
 			.keys()
 			.enumerate()
@@ -111,7 +159,9 @@ impl Day8 {
 				// Somehow this input problem has them all be the same length, from the initial run to
 				// every Z cycle, which makes this mathy-easy, but if input is fed into this that
 				// doesn't meet this requirement then bail out now.
-				bail!("Invalid input, unhandled differing stride length: {counts:?}");
+				bail!(AssumptionFailed(format!(
+					"unhandled differing stride length: {counts:?}"
+				)));
 			}
 			cycles.push(counts.first().copied().context("counts is empty")?);
 			counts.clear();
@@ -123,7 +173,7 @@ impl Day8 {
 			.reduce(|a, b| a.lcm(&b))
 			.context("cycles is empty")?;
 
-		Ok((score1, score2))
+		Ok(((score1, score2), export))
 	}
 }
 
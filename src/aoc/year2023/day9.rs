@@ -1,6 +1,6 @@
 use crate::aoc::helpers::*;
 use crate::AocApp;
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 use itertools::Itertools;
 
@@ -14,53 +14,64 @@ pub struct Day9 {
 }
 
 impl Day9 {
+	pub const TITLE: &str = "Mirage Maintenance";
+
 	pub fn run(&self, _app: &AocApp) -> anyhow::Result<(i64, i64)> {
-		let values = map_trimmed_nonempty_lines_of_file(&self.input, |line| {
-			Ok(line
-				.split_whitespace()
-				.map(str::parse)
-				.collect::<Result<ValuesVec, _>>()?)
+		self.input.solve(Self::solve)
+	}
+
+	pub fn solve(input: &str) -> anyhow::Result<(i64, i64)> {
+		let values = map_trimmed_nonempty_lines_of_file(input, |line| {
+			let mut values = ValuesVec::new();
+			for value in line.split_whitespace() {
+				if values.try_push(value.parse()?).is_some() {
+					bail!("more than {} values", values.capacity());
+				}
+			}
+			if values.is_empty() {
+				bail!("no values");
+			}
+			Ok(values)
 		})?;
 
-		let (front, back) = values
-			.iter()
-			.map(|v| differences_predicted(v))
-			.reduce(|f, b| (f.0 + b.0, f.1 + b.1))
-			.context("values input is empty")?;
+		if values.is_empty() {
+			bail!("values input is empty");
+		}
+		let (mut front, mut back) = (0i64, 0i64);
+		for values in &values {
+			let (f, b) = differences_predicted(values).context("values too large to predict")?;
+			front = front
+				.checked_add(f)
+				.context("predictions too large to add")?;
+			back = back
+				.checked_add(b)
+				.context("predictions too large to add")?;
+		}
 
 		Ok((back, front))
 	}
 }
 
-fn differences_predicted(values: &[i64]) -> (i64, i64) {
+fn differences_predicted(values: &[i64]) -> Option<(i64, i64)> {
 	let predictions = values
 		.iter()
 		.copied()
 		.tuple_windows()
-		.map(|(a, b)| b - a)
-		.collect::<ValuesVec>();
+		.map(|(a, b)| b.checked_sub(a))
+		.collect::<Option<ValuesVec>>()?;
 	let (front_prediction, back_prediction) = match predictions.len() {
 		0 => (0, 0),
 		1 => (predictions[0], predictions[0]),
 		_ if predictions.iter().copied().dedup().count() == 1 => (predictions[0], predictions[0]),
-		_ => differences_predicted(&predictions),
+		_ => differences_predicted(&predictions)?,
 	};
-	(
-		values[0] - front_prediction,
-		values[values.len() - 1] + back_prediction,
+	Option::zip(
+		values[0].checked_sub(front_prediction),
+		values[values.len() - 1].checked_add(back_prediction),
 	)
 }
 
 #[cfg(test)]
 mod tests {
-	crate::run_basic_tests!(
-		super::Day9,
-		example: (
-			"0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45
-",
-			(114, 2),
-		),
-	);
+	crate::run_basic_tests!(super::Day9, examples: (2023, 9));
 }
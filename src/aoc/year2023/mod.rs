@@ -24,9 +24,21 @@ pub use day7::Day7;
 pub use day8::Day8;
 pub use day9::Day9;
 
+#[cfg(feature = "cli")]
 use crate::AocApp;
 use clap::Parser;
 
+/// The registered days of 2023, appended as a `[DayN, ...]` list to the arguments of the given
+/// macro, so everything generated for every day uses the same list.
+#[macro_export]
+macro_rules! year2023_days {
+	($($callback:ident)::+!($($args:tt)*)) => {
+		$($callback)::+! { $($args)* [
+			Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8, Day9, Day10, Day11, Day12
+		] }
+	};
+}
+
 #[derive(Debug, Parser)]
 pub enum Year2023 {
 	/// Run all the Advent of Code 2023 days
@@ -69,21 +81,13 @@ pub enum Year2023 {
 	Day12(Day12),
 }
 
+#[cfg(feature = "cli")]
 impl Year2023 {
 	pub fn run(&self, app: &AocApp) -> anyhow::Result<()> {
-		crate::run_days!(
-			Year2023,
-			self,
-			app,
-			[Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8, Day9, Day10, Day11, Day12]
-		)
+		crate::year2023_days!(crate::run_days!(Year2023, self, app,))
 	}
 
 	pub fn run_all(app: &AocApp) -> anyhow::Result<()> {
-		crate::run_all_days!(
-			Year2023,
-			app,
-			[Day1, Day2, Day3, Day4, Day5, Day6, Day7, Day8, Day9, Day10, Day11, Day12]
-		)
+		crate::year2023_days!(crate::run_all_days!(Year2023, app,))
 	}
 }
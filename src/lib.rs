@@ -7,9 +7,16 @@
 )]
 pub mod aoc;
 
+pub use aoc::solve::{days, solve, Answers, DayInfo};
+
+use anyhow::Context;
 use clap::Parser;
+use std::path::PathBuf;
+use std::sync::Mutex;
+#[cfg(feature = "cli")]
 use std::time::Instant;
 
+#[cfg(feature = "cli")]
 #[derive(rust_embed::RustEmbed)]
 #[folder = "inputs"]
 pub struct Inputs;
@@ -22,24 +29,190 @@ pub struct AocApp {
 	/// Do not show the scores (quiet/spoiler mode)
 	#[clap(short = 'q', long)]
 	pub hide_scores: bool,
+	/// Trace what a day does, given as `<year>:<day>`, can be used multiple times.  `-vvv` traces
+	/// every day at the debug level and `-vvvv` at every level, and `RUST_LOG` filters further
+	#[clap(long, global = true, value_name = "YEAR:DAY", value_parser = parse_year_day)]
+	pub trace: Vec<(u16, u8)>,
+	/// Write traces to this file instead of stderr
+	#[clap(long, global = true)]
+	pub trace_file: Option<PathBuf>,
+	/// Recompute every day in `run-all` rather than reusing the answers cached by earlier runs of
+	/// the same day on the same input.  Days that render or export a graph, or run with `-vv`,
+	/// always run so that their files are written
+	#[clap(long, global = true)]
+	pub no_cache: bool,
+	/// How to print the answers of the days run
+	#[clap(long, global = true, value_enum, default_value_t)]
+	pub format: OutputFormat,
+	/// Directory to read the internal inputs from, as `<year>/dayDD.input`, falling back to the
+	/// inputs built in for those it doesn't have
+	#[clap(long, global = true)]
+	pub inputs_dir: Option<PathBuf>,
+	/// Directory to write generated files, like renders and graphs, into
+	#[clap(long, global = true, default_value = ".")]
+	pub artifacts_dir: PathBuf,
+	/// The files generated by the days run so far, not yet reported
+	#[clap(skip)]
+	pub artifacts: Mutex<Vec<PathBuf>>,
 	/// The command to execute
 	#[clap(subcommand)]
 	pub command: AocAppCommand,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+	/// Headers and answers for reading, with progress shown for slow days on a terminal
+	#[default]
+	Text,
+	/// A JSON object per day on a line of its own, and nothing else
+	Json,
+}
+
 impl AocApp {
+	/// The path of a file generated by a day, named `<year>-<day>-<kind>.<ext>` in the artifacts
+	/// directory, which is created if needed.  An empty `ext` names a directory of files instead.
+	/// The path is reported once the day is done.
+	pub fn artifact_path(
+		&self,
+		year: u16,
+		day: u8,
+		kind: &str,
+		ext: &str,
+	) -> anyhow::Result<PathBuf> {
+		std::fs::create_dir_all(&self.artifacts_dir).with_context(|| {
+			format!(
+				"failed to create artifacts directory {}",
+				self.artifacts_dir.display()
+			)
+		})?;
+		let mut name = format!("{year}-{day:02}-{kind}");
+		if !ext.is_empty() {
+			name.push('.');
+			name.push_str(ext);
+		}
+		let path = self.artifacts_dir.join(name);
+		self.add_artifact(path.clone());
+		Ok(path)
+	}
+
+	/// Report a file generated by a day at a path chosen by the user.
+	pub fn add_artifact(&self, path: PathBuf) {
+		self.artifacts
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+			.push(path);
+	}
+
+	/// The files generated since the last call.
+	pub fn take_artifacts(&self) -> Vec<PathBuf> {
+		std::mem::take(
+			&mut self
+				.artifacts
+				.lock()
+				.unwrap_or_else(std::sync::PoisonError::into_inner),
+		)
+	}
+
+	/// Send the trace events of the days asked for by `--trace`, `-vvv` or `RUST_LOG` to stderr or
+	/// the trace file.
+	#[cfg(feature = "cli")]
+	pub fn init_tracing(&self) -> anyhow::Result<()> {
+		use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+		let level = match self.verbose {
+			0..=2 => LevelFilter::OFF,
+			3 => LevelFilter::DEBUG,
+			_ => LevelFilter::TRACE,
+		};
+		if level == LevelFilter::OFF
+			&& self.trace.is_empty()
+			&& std::env::var_os(EnvFilter::DEFAULT_ENV).is_none()
+		{
+			return Ok(());
+		}
+		let mut filter = EnvFilter::builder()
+			.with_default_directive(LevelFilter::OFF.into())
+			.from_env_lossy()
+			.add_directive(format!("advent_of_code={level}").parse()?);
+		for (year, day) in &self.trace {
+			filter = filter
+				.add_directive(format!("advent_of_code::aoc::year{year}::day{day}=trace").parse()?);
+		}
+		let subscriber = tracing_subscriber::fmt()
+			.with_env_filter(filter)
+			.without_time();
+		let res = if let Some(path) = &self.trace_file {
+			let file = std::fs::File::create(path)
+				.with_context(|| format!("failed to create trace file {}", path.display()))?;
+			subscriber
+				.with_writer(std::sync::Mutex::new(file))
+				.with_ansi(false)
+				.try_init()
+		} else {
+			subscriber
+				.with_writer(std::io::stderr)
+				.with_ansi(std::io::IsTerminal::is_terminal(&std::io::stderr()))
+				.try_init()
+		};
+		// Already set up if called more than once, so nothing to do
+		let _ = res;
+		Ok(())
+	}
+
+	/// Parse the command line, with the defaults of its options from the config files, see
+	/// [`aoc::config::Config`].  The `solve-input` of `serve` reads no config, so that the answers
+	/// served don't depend on the config where it runs, nor fail on a broken one.
+	#[cfg(feature = "cli")]
+	pub fn parse_with_config() -> anyhow::Result<Self> {
+		use clap::{CommandFactory, FromArgMatches};
+		if let Ok(mut matches) = Self::command().try_get_matches() {
+			if matches.subcommand_name() == Some("solve-input") {
+				return Ok(Self::from_arg_matches_mut(&mut matches)?);
+			}
+		}
+		let config = aoc::config::Config::load()?;
+		let mut matches = config.apply(Self::command())?.get_matches();
+		config.install();
+		Ok(Self::from_arg_matches_mut(&mut matches)?)
+	}
+
+	#[cfg(feature = "cli")]
 	pub fn run(&self) -> anyhow::Result<()> {
+		self.init_tracing()?;
+		if let Some(dir) = &self.inputs_dir {
+			aoc::helpers::set_inputs_dir(dir.clone());
+		}
 		match &self.command {
 			AocAppCommand::Run(aoc) => aoc.run(self),
 			AocAppCommand::RunAll => {
-				println!("# OvermindDL1's Advent Of Code");
+				let text = self.format == OutputFormat::Text;
+				if text {
+					println!("# OvermindDL1's Advent Of Code");
+				}
 				let start = Instant::now();
 				let res = aoc::AocYear::run_all(self);
-				if self.verbose >= 1 {
+				if text && self.verbose >= 1 {
 					println!("_All Time Taken: {:?}_", start.elapsed());
 				}
 				res
 			}
+			AocAppCommand::Batch(args) => aoc::batch::run(args, self),
+			AocAppCommand::Bench(args) => aoc::bench::run(args, self),
+			AocAppCommand::CheckInput { year, day, input } => {
+				let input = input
+					.clone()
+					.unwrap_or_else(|| aoc::helpers::DataFrom::internal(*year, *day));
+				aoc::check_input::run(*year, *day, &input)
+			}
+			AocAppCommand::CompareImpls(args) => aoc::compare_impls::run(args, self),
+			AocAppCommand::Gen {
+				year,
+				day,
+				size,
+				seed,
+			} => aoc::generate::run_gen(*year, *day, *size, *seed),
+			AocAppCommand::Scale(args) => aoc::generate::run_scale(args, self),
+			AocAppCommand::Serve(args) => aoc::serve::run(args),
+			AocAppCommand::SolveInput { year, day } => aoc::serve::solve_input(*year, *day),
 			AocAppCommand::TUI => {
 				todo!("still need to make the TUI")
 			}
@@ -47,10 +220,60 @@ impl AocApp {
 	}
 }
 
+fn parse_year_day(s: &str) -> anyhow::Result<(u16, u8)> {
+	let (year, day) = s.split_once(':').context("expected `<year>:<day>`")?;
+	Ok((year.parse()?, day.parse()?))
+}
+
 #[derive(Debug, Parser)]
 pub enum AocAppCommand {
 	#[clap(flatten)]
 	Run(aoc::AocYear),
 	RunAll,
+	/// Run a day on every input file in a directory, showing the answers, time and whether each
+	/// one solved, failed, or broke an assumption the day makes about its input
+	#[cfg(feature = "cli")]
+	Batch(aoc::batch::BatchArgs),
+	/// Time days by the wall clock over many runs, optionally comparing against a saved baseline
+	#[cfg(feature = "cli")]
+	Bench(aoc::bench::BenchArgs),
+	/// Check that an input has the expected shape for a day without solving it, reporting every
+	/// problem found
+	CheckInput {
+		year: u16,
+		day: u8,
+		/// The input to check, defaults to the internal input of the day
+		input: Option<aoc::helpers::DataFrom>,
+	},
+	/// Run every implementation of a day, checking that they agree and showing how long each takes
+	#[cfg(feature = "cli")]
+	CompareImpls(aoc::compare_impls::CompareImplsArgs),
+	/// Generate a valid input for a day, as large as asked for, to stress its solution
+	#[cfg(feature = "cli")]
+	Gen {
+		year: u16,
+		day: u8,
+		/// How large an input to make, what it counts depends on the day
+		#[clap(long)]
+		size: usize,
+		/// The seed of the random input, the same seed and size always make the same input
+		#[clap(long, default_value_t = 0)]
+		seed: u64,
+	},
+	/// Time a day over generated inputs of growing sizes and fit a curve to how its time grows
+	#[cfg(feature = "cli")]
+	Scale(aoc::generate::ScaleArgs),
+	/// Serve solving days over HTTP: `GET /days`, `GET /status`, and `POST /solve/<year>/<day>`
+	/// with the input as the body, answering with JSON
+	#[cfg(feature = "cli")]
+	Serve(aoc::serve::ServeArgs),
+	/// Solve an input read from stdin and print the answers as JSON, which `serve` runs for each
+	/// input so that it can kill one that runs out of time
+	#[cfg(feature = "cli")]
+	#[clap(hide = true)]
+	SolveInput {
+		year: u16,
+		day: u8,
+	},
 	TUI,
 }
@@ -1,9 +1,8 @@
 #![warn(clippy::pedantic)]
 use advent_of_code::AocApp;
-use clap::Parser;
 
 fn main() -> anyhow::Result<()> {
-	let args = AocApp::parse();
+	let args = AocApp::parse_with_config()?;
 	if args.verbose > 2 {
 		println!("{args:?}");
 	}
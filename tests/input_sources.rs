@@ -0,0 +1,38 @@
+//! Inputs piped through stdin must give the same answers as the embedded ones,
+//! whatever line endings or trailing blank lines they were saved with.
+#![cfg(feature = "year2022")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn run(args: &[&str], stdin: Option<&[u8]>) -> String {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_advent_of_code"))
+		.args(args)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.expect("failed to spawn advent_of_code");
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(stdin.unwrap_or_default())
+		.unwrap();
+	let output = child.wait_with_output().unwrap();
+	assert!(output.status.success(), "{args:?} failed");
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn stdin_matches_internal() {
+	let expected = run(&["2022", "1", ":2022:1"], None);
+	let raw = std::fs::read_to_string("inputs/2022/day01.input").unwrap();
+	let crlf = raw.replace('\n', "\r\n");
+	for variant in [
+		raw.clone(),
+		format!("{raw}\n\n"),
+		format!("\u{feff}{crlf}\r\n"),
+	] {
+		assert_eq!(run(&["2022", "1", "-"], Some(variant.as_bytes())), expected);
+	}
+}
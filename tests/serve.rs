@@ -0,0 +1,144 @@
+//! The solve server, run as its own program since it solves every input in a child process of it.
+#![cfg(all(feature = "cli", feature = "year2022", feature = "year2023"))]
+
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+
+/// A running server, killed once dropped.
+struct Server {
+	child: Child,
+	port: u16,
+}
+
+impl Server {
+	fn start(args: &[&str]) -> Self {
+		Self::start_in(Path::new("."), args)
+	}
+
+	fn start_in(dir: &Path, args: &[&str]) -> Self {
+		let mut child = Command::new(env!("CARGO_BIN_EXE_advent_of_code"))
+			.current_dir(dir)
+			.args(["serve", "--port", "0"])
+			.args(args)
+			.stdout(Stdio::piped())
+			.spawn()
+			.expect("failed to spawn advent_of_code");
+		let mut line = String::new();
+		BufReader::new(child.stdout.take().unwrap())
+			.read_line(&mut line)
+			.unwrap();
+		let port = line
+			.trim()
+			.rsplit(':')
+			.next()
+			.and_then(|port| port.parse().ok())
+			.unwrap_or_else(|| panic!("unexpected first line {line:?}"));
+		Server { child, port }
+	}
+
+	fn request(&self, request: &str) -> (u16, Value) {
+		let mut stream = TcpStream::connect(("127.0.0.1", self.port)).unwrap();
+		stream.write_all(request.as_bytes()).unwrap();
+		let mut response = String::new();
+		stream.read_to_string(&mut response).unwrap();
+		let (head, body) = response.split_once("\r\n\r\n").unwrap();
+		let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+		(status, serde_json::from_str(body).unwrap())
+	}
+}
+
+impl Drop for Server {
+	fn drop(&mut self) {
+		let _ = self.child.kill();
+		let _ = self.child.wait();
+	}
+}
+
+fn post(path: &str, body: &str) -> String {
+	format!(
+		"POST {path} HTTP/1.1\r\nHost: test\r\nContent-Length: {}\r\n\r\n{body}",
+		body.len()
+	)
+}
+
+const EXAMPLE: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+
+#[test]
+fn endpoints() {
+	let server = Server::start(&["--timeout", "30", "--max-input", "1024", "-j", "2"]);
+
+	let (status, days) = server.request("GET /days HTTP/1.1\r\n\r\n");
+	assert_eq!(status, 200);
+	assert!(days
+		.as_array()
+		.unwrap()
+		.iter()
+		.any(|d| d["year"] == 2023 && d["day"] == 1 && d["title"] == "Trebuchet?!"));
+
+	let (status, solved) = server.request(&post("/solve/2023/1", EXAMPLE));
+	assert_eq!(status, 200);
+	assert_eq!(solved["status"], "ok");
+	assert_eq!(
+		(&solved["part1"], &solved["part2"]),
+		(&"142".into(), &"142".into())
+	);
+	assert!(solved["nanos"].is_u64());
+
+	let (status, failed) = server.request(&post("/solve/2022/1", "nope\n"));
+	assert_eq!(status, 422);
+	assert_eq!(failed["status"], "failed");
+	let (status, _) = server.request(&post("/solve/2023/26", EXAMPLE));
+	assert_eq!(status, 404);
+	let (status, _) = server.request(&post("/solve/2023/1", &"1\n".repeat(1000)));
+	assert_eq!(status, 413);
+	let (status, _) = server.request("GET /solve/2023/1 HTTP/1.1\r\n\r\n");
+	assert_eq!(status, 405);
+
+	let (status, status_body) = server.request("GET /status HTTP/1.1\r\n\r\n");
+	assert_eq!(status, 200);
+	assert_eq!(
+		(&status_body["solved"], &status_body["failed"]),
+		(&1.into(), &1.into())
+	);
+}
+
+#[test]
+fn timed_out_solves_free_their_job() {
+	let server = Server::start(&["--timeout", "1", "-j", "1"]);
+	// Takes several seconds to solve, far past the timeout
+	let slow = advent_of_code::aoc::generate::generator(2022, 8)
+		.unwrap()
+		.generate(0, 4000);
+
+	let (status, _) = server.request(&post("/solve/2022/8", &slow));
+	assert_eq!(status, 504);
+	// With the slow solve still holding the only job this would wait for it and time out too
+	let (status, solved) = server.request(&post("/solve/2023/1", EXAMPLE));
+	assert_eq!(status, 200);
+	assert_eq!(solved["part1"], "142");
+
+	let (_, status_body) = server.request("GET /status HTTP/1.1\r\n\r\n");
+	assert_eq!(
+		(&status_body["solved"], &status_body["timed_out"]),
+		(&1.into(), &1.into())
+	);
+}
+
+#[test]
+fn solves_ignore_the_config() {
+	let dir = std::env::temp_dir().join(format!("aoc-serve-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let server = Server::start_in(&dir, &[]);
+	// Only broken once the server is up, so that only the solves could read it
+	std::fs::write(dir.join("aoc.toml"), "verbose = \"loud\"\n").unwrap();
+
+	let (status, solved) = server.request(&post("/solve/2023/1", EXAMPLE));
+	assert_eq!(status, 200);
+	assert_eq!(solved["part1"], "142");
+
+	drop(server);
+	std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,12 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc)]
+use wasm_bindgen::prelude::*;
+
+/// Solve both parts of `day` of `year` for `input`, giving the answers as JSON like
+/// `{"part1":"142","part2":"281"}`, or throwing an error saying why the day couldn't be solved.
+#[wasm_bindgen]
+pub fn solve(year: u16, day: u8, input: &str) -> Result<String, JsError> {
+	let answers =
+		advent_of_code::solve(year, day, input).map_err(|err| JsError::new(&format!("{err:#}")))?;
+	Ok(serde_json::to_string(&answers)?)
+}
@@ -0,0 +1,13 @@
+use advent_of_code_wasm::solve;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn solves_days() {
+	let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+	assert_eq!(
+		solve(2023, 1, input).unwrap(),
+		r#"{"part1":"142","part2":"142"}"#
+	);
+	assert!(solve(2023, 26, input).is_err());
+	assert!(solve(2022, 1, "not a number\n").is_err());
+}